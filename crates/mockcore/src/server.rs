@@ -251,14 +251,26 @@ impl Api for Server {
     &self,
     txid: Txid,
     vout: u32,
-    _include_mempool: Option<bool>,
+    include_mempool: Option<bool>,
   ) -> Result<Option<GetTxOutResult>, jsonrpc_core::Error> {
     let state = self.state();
 
-    let Some(value) = state.utxos.get(&OutPoint { txid, vout }) else {
+    let outpoint = OutPoint { txid, vout };
+
+    let Some(value) = state.utxos.get(&outpoint) else {
       return Ok(None);
     };
 
+    if include_mempool.unwrap_or(true)
+      && state
+        .mempool
+        .iter()
+        .flat_map(|tx| &tx.input)
+        .any(|input| input.previous_output == outpoint)
+    {
+      return Ok(None);
+    }
+
     let mut confirmations = None;
 
     for (height, hash) in state.hashes.iter().enumerate() {
@@ -1,6 +1,8 @@
 use super::*;
 
-#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, DeserializeFromStr, SerializeDisplay)]
+#[derive(
+  Debug, PartialEq, Eq, Hash, PartialOrd, Copy, Clone, DeserializeFromStr, SerializeDisplay,
+)]
 pub enum Rarity {
   Common,
   Uncommon,
@@ -37,6 +37,13 @@ impl Block {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockAtTime {
+  pub hash: BlockHash,
+  pub height: u32,
+  pub timestamp: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct BlockInfo {
   pub average_fee: u64,
@@ -114,6 +121,13 @@ pub struct InscriptionRecursive {
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionLocation {
+  pub address: Option<String>,
+  pub height: u32,
+  pub satpoint: SatPoint,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
@@ -121,7 +135,7 @@ pub struct Inscriptions {
   pub page_index: u32,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
@@ -162,6 +176,27 @@ impl Output {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OutputError {
+  pub error: String,
+  pub outpoint: OutPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RareSat {
+  pub offset: u64,
+  pub rarity: Rarity,
+  pub sat: ordinals::Sat,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanPool {
+  pub active: usize,
+  pub completed: u64,
+  pub queued: usize,
+  pub threads: usize,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub block: u32,
@@ -181,6 +216,12 @@ pub struct Sat {
   pub timestamp: i64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatRarities {
+  pub charms: Vec<Charm>,
+  pub rarity: Rarity,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SatInscription {
   pub id: Option<InscriptionId>,
@@ -192,3 +233,94 @@ pub struct SatInscriptions {
   pub more: bool,
   pub page: u64,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatMatch {
+  pub sat: ordinals::Sat,
+  pub satpoint: Option<SatPoint>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatMatches {
+  pub more: bool,
+  pub page: usize,
+  pub sats: Vec<SatMatch>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatName {
+  pub name: String,
+  pub sat: ordinals::Sat,
+  pub satpoint: Option<SatPoint>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatNames {
+  pub more: bool,
+  pub page: usize,
+  pub sats: Vec<SatName>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatRangeInscription {
+  pub charms: Vec<Charm>,
+  pub id: InscriptionId,
+  pub sat: Option<ordinals::Sat>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatRanges {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub inscriptions: Option<Vec<Vec<SatRangeInscription>>>,
+  pub more: bool,
+  pub page: usize,
+  pub ranges: Vec<(u64, u64)>,
+  pub script_pubkey: Option<String>,
+  pub value: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRarities {
+  pub outputs: Vec<OutputRarities>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OutputRarities {
+  pub outpoint: OutPoint,
+  pub rare_sats: Vec<RareSat>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UtxoRarities {
+  pub rare_sats: Vec<RareSat>,
+  pub sat_ranges: Vec<(u64, u64)>,
+  pub spent: bool,
+}
+
+impl UtxoRarities {
+  pub(crate) fn new(sat_ranges: Vec<(u64, u64)>, spent: bool) -> Self {
+    let mut rare_sats = Vec::new();
+    let mut offset = 0;
+
+    for (start, end) in &sat_ranges {
+      let sat = ordinals::Sat(*start);
+      let rarity = sat.rarity();
+
+      if rarity > Rarity::Common {
+        rare_sats.push(RareSat {
+          offset,
+          rarity,
+          sat,
+        });
+      }
+
+      offset += end - start;
+    }
+
+    Self {
+      rare_sats,
+      sat_ranges,
+      spent,
+    }
+  }
+}
@@ -4,7 +4,7 @@ use {
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
       OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
     },
-    event::Event,
+    event::{Event, EventSender},
     lot::Lot,
     reorg::Reorg,
     updater::Updater,
@@ -35,23 +35,29 @@ use {
   },
 };
 
-pub use self::entry::RuneEntry;
+pub use self::{entry::RuneEntry, event_filter::EventFilter};
 
 pub(crate) mod entry;
 pub mod event;
+mod event_filter;
 mod fetcher;
 mod lot;
 mod reorg;
-mod rtx;
+pub(crate) mod rtx;
+pub mod snapshot;
 mod updater;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 25;
+const SCHEMA_VERSION: u64 = 27;
+
+// number of preceding blocks whose median a block timestamp must exceed
+const MEDIAN_TIME_BLOCKS: u32 = 11;
 
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_table! { CONTENT_TYPE_TO_COUNT, Option<&[u8]>, u64 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
@@ -61,10 +67,12 @@ define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT, (u32, u32), &SatPointValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
@@ -90,6 +98,9 @@ pub(crate) enum Statistic {
   IndexTransactions = 12,
   IndexSpentSats = 13,
   InitialSyncTime = 14,
+  IndexAddresses = 15,
+  UntrackedRarities = 16,
+  IndexInscriptionHistory = 17,
 }
 
 impl Statistic {
@@ -184,11 +195,13 @@ pub struct Index {
   pub(crate) client: Client,
   database: Database,
   durability: redb::Durability,
-  event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  event_sender: Option<EventSender>,
   first_inscription_height: u32,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
+  index_inscription_history: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
@@ -197,6 +210,7 @@ pub struct Index {
   path: PathBuf,
   started: DateTime<Utc>,
   unrecoverably_reorged: AtomicBool,
+  untracked_rarities: HashSet<Rarity>,
 }
 
 impl Index {
@@ -298,6 +312,7 @@ impl Index {
 
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
         tx.open_table(CONTENT_TYPE_TO_COUNT)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
@@ -306,10 +321,12 @@ impl Index {
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -324,6 +341,18 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(settings.index_addresses()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexInscriptionHistory,
+            u64::from(settings.index_inscription_history()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
@@ -348,6 +377,15 @@ impl Index {
             u64::from(settings.index_transactions()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::UntrackedRarities,
+            settings
+              .untracked_rarities()
+              .map(|rarity| 1 << u8::from(rarity))
+              .fold(0, |mask, bit| mask | bit),
+          )?;
+
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
@@ -402,18 +440,34 @@ impl Index {
       Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_addresses;
+    let index_inscription_history;
     let index_runes;
     let index_sats;
     let index_spent_sats;
     let index_transactions;
+    let untracked_rarities;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_inscription_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionHistory)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
       index_spent_sats = Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+
+      let mask = statistics
+        .get(&Statistic::UntrackedRarities.key())?
+        .map(|guard| guard.value())
+        .unwrap_or_default();
+
+      untracked_rarities = (0..u64::BITS)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .filter_map(|bit| Rarity::try_from(u8::try_from(bit).ok()?).ok())
+        .collect();
     }
 
     let genesis_block_coinbase_transaction =
@@ -424,10 +478,15 @@ impl Index {
       client,
       database,
       durability,
-      event_sender,
+      event_sender: event_sender.map(|sender| EventSender {
+        filter: settings.event_filter().cloned(),
+        sender,
+      }),
       first_inscription_height: settings.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      index_addresses,
+      index_inscription_history,
       index_runes,
       index_sats,
       index_spent_sats,
@@ -436,6 +495,7 @@ impl Index {
       path,
       started: Utc::now(),
       unrecoverably_reorged: AtomicBool::new(false),
+      untracked_rarities,
     })
   }
 
@@ -444,15 +504,12 @@ impl Index {
     self.durability = durability;
   }
 
-  pub(crate) fn contains_output(&self, output: &OutPoint) -> Result<bool> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(OUTPOINT_TO_VALUE)?
-        .get(&output.store())?
-        .is_some(),
-    )
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn has_inscription_history_index(&self) -> bool {
+    self.index_inscription_history
   }
 
   pub(crate) fn has_rune_index(&self) -> bool {
@@ -463,6 +520,12 @@ impl Index {
     self.index_sats
   }
 
+  /// Whether the location of `sat` is stored in `SAT_TO_SATPOINT`. Common sats
+  /// and sats of untracked rarities must instead be found in the sat ranges.
+  pub(crate) fn tracks_sat(&self, sat: Sat) -> bool {
+    !sat.common() && !self.untracked_rarities.contains(&sat.rarity())
+  }
+
   pub(crate) fn status(&self) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -505,9 +568,16 @@ impl Index {
       chain: self.settings.chain(),
       content_type_counts,
       cursed_inscriptions,
+      git_branch: Some(env!("GIT_BRANCH"))
+        .filter(|branch| !branch.is_empty())
+        .map(Into::into),
+      git_commit: Some(env!("GIT_COMMIT"))
+        .filter(|commit| !commit.is_empty())
+        .map(Into::into),
       height,
       initial_sync_time: Duration::from_micros(initial_sync_time),
       inscriptions: blessed_inscriptions + cursed_inscriptions,
+      instance_id: self.settings.instance_id().map(Into::into),
       lost_sats: statistic(Statistic::LostSats)?,
       minimum_rune_for_next_block: Rune::minimum_at_height(
         self.settings.chain().network(),
@@ -516,10 +586,12 @@ impl Index {
       rune_index: statistic(Statistic::IndexRunes)? != 0,
       runes: statistic(Statistic::Runes)?,
       sat_index: statistic(Statistic::IndexSats)? != 0,
+      scan_pool: api::ScanPool::default(),
       started: self.started,
       transaction_index: statistic(Statistic::IndexTransactions)? != 0,
       unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
       uptime: (Utc::now() - self.started).to_std()?,
+      version: env!("CARGO_PKG_VERSION").into(),
     })
   }
 
@@ -651,8 +723,17 @@ impl Index {
     }
   }
 
-  pub(crate) fn export(&self, filename: &String, include_addresses: bool) -> Result {
-    let mut writer = BufWriter::new(fs::File::create(filename)?);
+  pub(crate) fn export(
+    &self,
+    filename: &String,
+    include_addresses: bool,
+    height: Option<u32>,
+  ) -> Result {
+    ensure!(
+      height.is_none() || self.index_inscription_history,
+      "exporting at a height requires index created with `--index-inscription-history` flag"
+    );
+
     let rtx = self.database.begin_read()?;
 
     let blocks_indexed = rtx
@@ -663,11 +744,26 @@ impl Index {
       .map(|(height, _header)| height.value() + 1)
       .unwrap_or(0);
 
-    writeln!(writer, "# export at block height {}", blocks_indexed)?;
+    if let Some(height) = height {
+      ensure!(
+        height < blocks_indexed,
+        "cannot export at height {height}, index has {blocks_indexed} blocks"
+      );
+    }
+
+    let mut writer = BufWriter::new(fs::File::create(filename)?);
+
+    writeln!(
+      writer,
+      "# export at block height {}",
+      height.map(|height| height + 1).unwrap_or(blocks_indexed)
+    )?;
 
     log::info!("exporting database tables to {filename}");
 
     let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let sequence_number_and_height_to_satpoint =
+      rtx.open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT)?;
 
     for result in rtx
       .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
@@ -676,12 +772,26 @@ impl Index {
       let entry = result?;
       let sequence_number = entry.0.value();
       let entry = InscriptionEntry::load(entry.1.value());
-      let satpoint = SatPoint::load(
-        *sequence_number_to_satpoint
-          .get(sequence_number)?
-          .unwrap()
-          .value(),
-      );
+
+      let satpoint = match height {
+        // sequence numbers are assigned in block order
+        Some(height) if entry.height > height => break,
+        Some(height) => SatPoint::load(
+          *sequence_number_and_height_to_satpoint
+            .range((sequence_number, 0)..=(sequence_number, height))?
+            .next_back()
+            .transpose()?
+            .unwrap()
+            .1
+            .value(),
+        ),
+        None => SatPoint::load(
+          *sequence_number_to_satpoint
+            .get(sequence_number)?
+            .unwrap()
+            .value(),
+        ),
+      };
 
       write!(
         writer,
@@ -719,7 +829,9 @@ impl Index {
     Ok(())
   }
 
-  fn begin_read(&self) -> Result<rtx::Rtx> {
+  /// Begin a read transaction. Every read made through it sees the index as
+  /// of a single height, even if blocks are committed in the meantime.
+  pub(crate) fn begin_read(&self) -> Result<rtx::Rtx> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
 
@@ -833,7 +945,105 @@ impl Index {
     Ok(result)
   }
 
+  pub(crate) fn get_rare_sat_satpoints_paginated(
+    &self,
+    rarity: Rarity,
+    page_size: usize,
+    page_index: usize,
+    cancelled: &AtomicBool,
+  ) -> Result<(Vec<(Sat, Option<SatPoint>)>, bool)> {
+    let rtx = self.begin_read()?;
+
+    let (sats, more) = Self::rare_sats_page(rarity, rtx.block_count()?, page_size, page_index);
+
+    if self.untracked_rarities.contains(&rarity) {
+      let satpoints = self.find_many(&sats, cancelled)?;
+      return Ok((sats.into_iter().zip(satpoints).collect(), more));
+    }
+
+    let sat_to_satpoint = rtx.0.open_table(SAT_TO_SATPOINT)?;
+
+    let mut satpoints = Vec::with_capacity(sats.len());
+
+    for sat in sats {
+      ensure!(!cancelled.load(atomic::Ordering::Relaxed), "scan cancelled");
+
+      satpoints.push((
+        sat,
+        sat_to_satpoint
+          .get(&sat.n())?
+          .map(|satpoint| Entry::load(*satpoint.value())),
+      ));
+    }
+
+    Ok((satpoints, more))
+  }
+
+  /// Sats of `rarity` on the given page of those mined in the first
+  /// `block_count` blocks, and whether there are more. Every non-common sat is
+  /// the first sat of a block, and its rarity depends only on the block's
+  /// height, so each sat is found by binary search over the number of earlier
+  /// blocks of that rarity rather than by scanning.
+  fn rare_sats_page(
+    rarity: Rarity,
+    block_count: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> (Vec<Sat>, bool) {
+    let total = usize::try_from(Self::rare_block_count(rarity, block_count)).unwrap();
+
+    let start = page_index.saturating_mul(page_size).min(total);
+    let end = start.saturating_add(page_size).min(total);
+
+    let sats = (start..end)
+      .map(|n| {
+        let n = u32::try_from(n).unwrap();
+
+        // find the lowest height whose block is the `n`th of `rarity`
+        let (mut low, mut high) = (0, block_count);
+
+        while low < high {
+          let mid = low + (high - low) / 2;
+
+          if Self::rare_block_count(rarity, mid + 1) > n {
+            high = mid;
+          } else {
+            low = mid + 1;
+          }
+        }
+
+        Height(low).starting_sat()
+      })
+      .collect();
+
+    (sats, end < total)
+  }
+
+  /// Number of blocks below `height` whose first sat has `rarity`.
+  fn rare_block_count(rarity: Rarity, height: u32) -> u32 {
+    let multiples = |interval: u32| height.div_ceil(interval);
+
+    let cycle = ordinals::CYCLE_EPOCHS * SUBSIDY_HALVING_INTERVAL;
+
+    match rarity {
+      Rarity::Common => 0,
+      Rarity::Uncommon => {
+        height + multiples(cycle)
+          - multiples(DIFFCHANGE_INTERVAL)
+          - multiples(SUBSIDY_HALVING_INTERVAL)
+      }
+      Rarity::Rare => multiples(DIFFCHANGE_INTERVAL) - multiples(cycle),
+      Rarity::Epic => multiples(SUBSIDY_HALVING_INTERVAL) - multiples(cycle),
+      Rarity::Legendary => multiples(cycle) - height.min(1),
+      Rarity::Mythic => height.min(1),
+    }
+  }
+
   pub(crate) fn rare_sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
+    if self.index_sats && !sat.common() && !self.tracks_sat(sat) {
+      return self.find(sat);
+    }
+
     Ok(
       self
         .database
@@ -970,43 +1180,6 @@ impl Index {
     Ok(((id, balance), len))
   }
 
-  pub(crate) fn get_rune_balances_for_outpoint(
-    &self,
-    outpoint: OutPoint,
-  ) -> Result<Vec<(SpacedRune, Pile)>> {
-    let rtx = self.database.begin_read()?;
-
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-
-    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-
-    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
-      return Ok(Vec::new());
-    };
-
-    let balances_buffer = balances.value();
-
-    let mut balances = Vec::new();
-    let mut i = 0;
-    while i < balances_buffer.len() {
-      let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
-      i += length;
-
-      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
-
-      balances.push((
-        entry.spaced_rune,
-        Pile {
-          amount,
-          divisibility: entry.divisibility,
-          symbol: entry.symbol,
-        },
-      ));
-    }
-
-    Ok(balances)
-  }
-
   pub(crate) fn get_rune_balance_map(
     &self,
   ) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
@@ -1153,7 +1326,6 @@ impl Index {
     Ok((collections, more))
   }
 
-  #[cfg(test)]
   pub(crate) fn get_children_by_inscription_id(
     &self,
     inscription_id: InscriptionId,
@@ -1439,6 +1611,87 @@ impl Index {
     Ok(satpoint)
   }
 
+  pub(crate) fn get_inscription_location_history(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<(u32, SatPoint)>>> {
+    ensure!(
+      self.index_inscription_history,
+      "inscription location history requires index created with `--index-inscription-history` flag"
+    );
+
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(Some(
+      rtx
+        .open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT)?
+        .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+        .map(|result| result.map(|(key, satpoint)| (key.value().1, Entry::load(*satpoint.value()))))
+        .collect::<Result<Vec<(u32, SatPoint)>, StorageError>>()?,
+    ))
+  }
+
+  pub(crate) fn get_inscription_satpoint_at_height(
+    &self,
+    inscription_id: InscriptionId,
+    height: u32,
+  ) -> Result<Option<SatPoint>> {
+    ensure!(
+      self.index_inscription_history,
+      "inscription location history requires index created with `--index-inscription-history` flag"
+    );
+
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let satpoint = rtx
+      .open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT)?
+      .range((sequence_number, 0)..=(sequence_number, height))?
+      .next_back()
+      .transpose()?
+      .map(|(_, satpoint)| Entry::load(*satpoint.value()));
+
+    Ok(satpoint)
+  }
+
+  pub(crate) fn get_address_for_satpoint(&self, satpoint: SatPoint) -> Result<Option<Address>> {
+    if satpoint.outpoint == unbound_outpoint() || satpoint.outpoint == OutPoint::null() {
+      return Ok(None);
+    }
+
+    Ok(
+      self
+        .get_transaction(satpoint.outpoint.txid)?
+        .and_then(|tx| {
+          tx.output
+            .into_iter()
+            .nth(satpoint.outpoint.vout.into_usize())
+        })
+        .and_then(|output| {
+          self
+            .settings
+            .chain()
+            .address_from_script(&output.script_pubkey)
+            .ok()
+        }),
+    )
+  }
+
   pub(crate) fn get_inscription_by_id(
     &self,
     inscription_id: InscriptionId,
@@ -1486,22 +1739,15 @@ impl Index {
     )
   }
 
+  #[cfg(test)]
   pub(crate) fn get_inscriptions_on_output_with_satpoints(
     &self,
     outpoint: OutPoint,
   ) -> Result<Vec<(SatPoint, InscriptionId)>> {
-    let rtx = self.database.begin_read()?;
-    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-
-    Self::inscriptions_on_output(
-      &satpoint_to_sequence_number,
-      &sequence_number_to_inscription_entry,
-      outpoint,
-    )
+    self.begin_read()?.inscriptions_on_output(outpoint)
   }
 
+  #[cfg(test)]
   pub(crate) fn get_inscriptions_on_output(
     &self,
     outpoint: OutPoint,
@@ -1516,18 +1762,17 @@ impl Index {
   }
 
   pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    self.get_transaction_at(&self.begin_read()?, txid)
+  }
+
+  fn get_transaction_at(&self, rtx: &rtx::Rtx, txid: Txid) -> Result<Option<Transaction>> {
     if txid == self.genesis_block_coinbase_txid {
       return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
     }
 
     if self.index_transactions {
-      if let Some(transaction) = self
-        .database
-        .begin_read()?
-        .open_table(TRANSACTION_ID_TO_TRANSACTION)?
-        .get(&txid.store())?
-      {
-        return Ok(Some(consensus::encode::deserialize(transaction.value())?));
+      if let Some(transaction) = rtx.transaction(txid)? {
+        return Ok(Some(transaction));
       }
     }
 
@@ -1535,31 +1780,61 @@ impl Index {
   }
 
   pub(crate) fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
-    let sat = sat.0;
+    Ok(
+      self
+        .find_many(&[sat], &AtomicBool::new(false))?
+        .pop()
+        .flatten(),
+    )
+  }
+
+  pub(crate) fn find_many(
+    &self,
+    sats: &[Sat],
+    cancelled: &AtomicBool,
+  ) -> Result<Vec<Option<SatPoint>>> {
     let rtx = self.begin_read()?;
 
-    if rtx.block_count()? <= Sat(sat).height().n() {
-      return Ok(None);
+    let block_count = rtx.block_count()?;
+
+    let mut satpoints = vec![None; sats.len()];
+
+    let mut remaining = sats
+      .iter()
+      .filter(|sat| sat.height().n() < block_count)
+      .count();
+
+    if remaining == 0 {
+      return Ok(satpoints);
     }
 
     let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
 
     for range in outpoint_to_sat_ranges.range::<&[u8; 36]>(&[0; 36]..)? {
+      ensure!(!cancelled.load(atomic::Ordering::Relaxed), "scan cancelled");
+
       let (key, value) = range?;
       let mut offset = 0;
       for chunk in value.value().chunks_exact(11) {
         let (start, end) = SatRange::load(chunk.try_into().unwrap());
-        if start <= sat && sat < end {
-          return Ok(Some(SatPoint {
-            outpoint: Entry::load(*key.value()),
-            offset: offset + sat - start,
-          }));
+        for (sat, satpoint) in sats.iter().zip(satpoints.iter_mut()) {
+          if satpoint.is_none() && start <= sat.0 && sat.0 < end {
+            *satpoint = Some(SatPoint {
+              outpoint: Entry::load(*key.value()),
+              offset: offset + sat.0 - start,
+            });
+            remaining -= 1;
+          }
         }
         offset += end - start;
       }
+
+      if remaining == 0 {
+        break;
+      }
     }
 
-    Ok(None)
+    Ok(satpoints)
   }
 
   pub(crate) fn find_range(
@@ -1616,20 +1891,7 @@ impl Index {
   }
 
   pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<Vec<(u64, u64)>>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(OUTPOINT_TO_SAT_RANGES)?
-        .get(&outpoint.store())?
-        .map(|outpoint| outpoint.value().to_vec())
-        .map(|sat_ranges| {
-          sat_ranges
-            .chunks_exact(11)
-            .map(|chunk| SatRange::load(chunk.try_into().unwrap()))
-            .collect::<Vec<(u64, u64)>>()
-        }),
-    )
+    self.begin_read()?.list(outpoint)
   }
 
   pub(crate) fn is_output_spent(&self, outpoint: OutPoint) -> Result<bool> {
@@ -1643,6 +1905,38 @@ impl Index {
     )
   }
 
+  /// Whether the transaction spending `outpoint` has at least `depth`
+  /// confirmations, so that the spend can no longer be undone by a plausible
+  /// reorg
+  pub(crate) fn is_output_spend_buried(&self, outpoint: OutPoint, depth: u32) -> Result<bool> {
+    if self
+      .client
+      .get_tx_out(&outpoint.txid, outpoint.vout, Some(false))?
+      .is_some()
+    {
+      return Ok(false);
+    }
+
+    let tip = u32::try_from(self.client.get_block_count()?).unwrap();
+
+    for height in (tip + 2).saturating_sub(depth)..=tip {
+      let Some(block) = self.get_block_by_height(height)? else {
+        return Ok(false);
+      };
+
+      if block
+        .txdata
+        .iter()
+        .flat_map(|tx| &tx.input)
+        .any(|input| input.previous_output == outpoint)
+      {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
   pub(crate) fn is_output_in_active_chain(&self, outpoint: OutPoint) -> Result<bool> {
     if outpoint == OutPoint::null() {
       return Ok(true);
@@ -1671,6 +1965,55 @@ impl Index {
     Ok(true)
   }
 
+  pub(crate) fn block_at_time(&self, timestamp: u32) -> Result<Option<(u32, Header)>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    let Some(last) = height_to_block_header
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value())
+    else {
+      return Ok(None);
+    };
+
+    let header = |height: u32| -> Result<Header> {
+      Ok(Header::load(
+        *height_to_block_header.get(height)?.unwrap().value(),
+      ))
+    };
+
+    // block timestamps are only roughly increasing, so binary search for the
+    // first block at or after `timestamp`, and then check nearby blocks
+    let (mut low, mut high) = (0, last + 1);
+
+    while low < high {
+      let middle = low + (high - low) / 2;
+      if header(middle)?.time < timestamp {
+        low = middle + 1;
+      } else {
+        high = middle;
+      }
+    }
+
+    let mut closest: Option<(u32, Header)> = None;
+
+    for height in low.saturating_sub(MEDIAN_TIME_BLOCKS)..=(low + MEDIAN_TIME_BLOCKS).min(last) {
+      let header = header(height)?;
+
+      if closest
+        .map(|(_, closest)| header.time.abs_diff(timestamp) < closest.time.abs_diff(timestamp))
+        .unwrap_or(true)
+      {
+        closest = Some((height, header));
+      }
+    }
+
+    Ok(closest)
+  }
+
   pub(crate) fn block_time(&self, height: Height) -> Result<Blocktime> {
     let height = height.n();
 
@@ -2117,8 +2460,8 @@ impl Index {
             .unwrap()
             .any(|entry| entry.unwrap().value() == sequence_number));
 
-          // we do not track common sats (only the sat ranges)
-          if !Sat(sat).common() {
+          // we do not track common sats or untracked rarities (only the sat ranges)
+          if self.tracks_sat(Sat(sat)) {
             assert_eq!(
               SatPoint::load(
                 *rtx
@@ -2186,8 +2529,14 @@ impl Index {
     )
   }
 
-  pub(crate) fn get_output_info(&self, outpoint: OutPoint) -> Result<Option<(api::Output, TxOut)>> {
-    let sat_ranges = self.list(outpoint)?;
+  /// Output info as of the height of `rtx`. Whether the output is spent, and
+  /// transactions missing from the transaction index, come from bitcoind.
+  pub(crate) fn get_output_info(
+    &self,
+    rtx: &rtx::Rtx,
+    outpoint: OutPoint,
+  ) -> Result<Option<(api::Output, TxOut)>> {
+    let sat_ranges = rtx.list(outpoint)?;
 
     let indexed;
 
@@ -2207,9 +2556,9 @@ impl Index {
         script_pubkey: ScriptBuf::new(),
       }
     } else {
-      indexed = self.contains_output(&outpoint)?;
+      indexed = rtx.contains_output(&outpoint)?;
 
-      let Some(tx) = self.get_transaction(outpoint.txid)? else {
+      let Some(tx) = self.get_transaction_at(rtx, outpoint.txid)? else {
         return Ok(None);
       };
 
@@ -2220,9 +2569,13 @@ impl Index {
       output
     };
 
-    let inscriptions = self.get_inscriptions_on_output(outpoint)?;
+    let inscriptions = rtx
+      .inscriptions_on_output(outpoint)?
+      .into_iter()
+      .map(|(_satpoint, inscription_id)| inscription_id)
+      .collect();
 
-    let runes = self.get_rune_balances_for_outpoint(outpoint)?;
+    let runes = rtx.rune_balances_for_outpoint(outpoint)?;
 
     let spent = self.is_output_spent(outpoint)?;
 
@@ -2397,6 +2750,27 @@ mod tests {
     )
   }
 
+  #[test]
+  fn read_transactions_are_not_affected_by_later_blocks() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    let rtx = context.index.begin_read().unwrap();
+
+    let txid = context.mine_blocks(1)[0].txdata[0].txid();
+
+    assert_eq!(rtx.block_height().unwrap(), Some(Height(0)));
+    assert_eq!(rtx.list(OutPoint::new(txid, 0)).unwrap(), None);
+    assert!(!rtx.contains_output(&OutPoint::new(txid, 0)).unwrap());
+
+    let rtx = context.index.begin_read().unwrap();
+
+    assert_eq!(rtx.block_height().unwrap(), Some(Height(1)));
+    assert_eq!(
+      rtx.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      &[(50 * COIN_VALUE, 100 * COIN_VALUE)],
+    );
+  }
+
   #[test]
   fn list_split_ranges_are_tracked_correctly() {
     let context = Context::builder().arg("--index-sats").build();
@@ -2534,11 +2908,72 @@ mod tests {
   }
 
   #[test]
-  fn list_null_input() {
+  fn list_zero_value_and_dust_outputs() {
     let context = Context::builder().arg("--index-sats").build();
 
     context.mine_blocks(1);
-    let no_value_output = TransactionTemplate {
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 4,
+      output_values: &[25 * COIN_VALUE, 0, 330, 25 * COIN_VALUE - 330],
+      fee: 0,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      &[(50 * COIN_VALUE, 75 * COIN_VALUE)],
+    );
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 1)).unwrap().unwrap(),
+      &[],
+    );
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 2)).unwrap().unwrap(),
+      &[(75 * COIN_VALUE, 75 * COIN_VALUE + 330)],
+    );
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 3)).unwrap().unwrap(),
+      &[(75 * COIN_VALUE + 330, 100 * COIN_VALUE)],
+    );
+
+    let merge_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 1, 1, Default::default()),
+        (2, 1, 2, Default::default()),
+        (2, 1, 3, Default::default()),
+      ],
+      fee: 0,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(merge_txid, 0))
+        .unwrap()
+        .unwrap(),
+      &[
+        (75 * COIN_VALUE, 75 * COIN_VALUE + 330),
+        (75 * COIN_VALUE + 330, 100 * COIN_VALUE)
+      ],
+    );
+  }
+
+  #[test]
+  fn list_null_input() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(1);
+    let no_value_output = TransactionTemplate {
       inputs: &[(1, 0, 0, Default::default())],
       fee: 50 * COIN_VALUE,
       ..default()
@@ -6191,6 +6626,37 @@ mod tests {
     assert!(context.index.list(outpoint).unwrap().is_some());
   }
 
+  #[test]
+  fn rare_sats_page_matches_scanning_block_subsidies() {
+    let block_count = 2 * ordinals::CYCLE_EPOCHS * SUBSIDY_HALVING_INTERVAL + 5000;
+
+    for rarity in [
+      Rarity::Uncommon,
+      Rarity::Rare,
+      Rarity::Epic,
+      Rarity::Legendary,
+      Rarity::Mythic,
+    ] {
+      let expected = (0..block_count)
+        .map(|height| Height(height).starting_sat())
+        .filter(|sat| sat.rarity() == rarity)
+        .collect::<Vec<Sat>>();
+
+      for page_size in [1, 7, 100] {
+        for page_index in [0, 1, 5, expected.len() / page_size, 1_000_000] {
+          let start = (page_index * page_size).min(expected.len());
+          let end = (start + page_size).min(expected.len());
+
+          assert_eq!(
+            Index::rare_sats_page(rarity, block_count, page_size, page_index),
+            (expected[start..end].to_vec(), end < expected.len()),
+            "{rarity} page {page_index} of size {page_size}",
+          );
+        }
+      }
+    }
+  }
+
   #[test]
   fn is_output_spent() {
     let context = Context::builder().build();
@@ -6318,9 +6784,11 @@ mod tests {
     };
     let create_event = event_receiver.blocking_recv().unwrap();
     let expected_charms = if context.index.index_sats { 513 } else { 0 };
+    let expected_sat = context.index.index_sats.then_some(Sat(50 * COIN_VALUE));
     assert_eq!(
       create_event,
       Event::InscriptionCreated {
+        bare_multisig: false,
         inscription_id,
         location: Some(SatPoint {
           outpoint: OutPoint {
@@ -6332,7 +6800,15 @@ mod tests {
         sequence_number: 0,
         block_height: 2,
         charms: expected_charms,
+        content_type_mismatch: false,
+        exceeds_limits: false,
+        flagged: false,
         parent_inscription_ids: Vec::new(),
+        protocol: None,
+        reinscription_of: Vec::new(),
+        sanctioned_counterparty: false,
+        sat: expected_sat,
+        traits: BTreeMap::new(),
       }
     );
 
@@ -6350,6 +6826,7 @@ mod tests {
     assert_eq!(
       transfer_event,
       Event::InscriptionTransferred {
+        bare_multisig: false,
         block_height: 3,
         inscription_id,
         new_location: SatPoint {
@@ -6366,11 +6843,910 @@ mod tests {
           },
           offset: 0
         },
+        sanctioned_counterparty: false,
+        self_transfer: true,
         sequence_number: 0,
       }
     );
   }
 
+  #[test]
+  fn event_filter_drops_non_matching_events() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .env("EVENT_FILTER", "event_type != inscription_created")
+      .env("NEW_SUPPLY_EVENTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      fee: 0,
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 0,
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+
+    while let Ok(event) = event_receiver.try_recv() {
+      events.push((event.event_type(), event.block_height()));
+    }
+
+    assert_eq!(
+      events,
+      [
+        ("new_supply", 0),
+        ("new_supply", 1),
+        ("new_supply", 2),
+        ("new_supply", 3),
+        ("inscription_transferred", 3),
+      ]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_entry(InscriptionId {
+          txid: create_txid,
+          index: 0,
+        })
+        .unwrap()
+        .unwrap()
+        .height,
+      2,
+    );
+  }
+
+  #[test]
+  fn events_are_ordered_by_block_and_transaction() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(3);
+
+    let first = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
+      ..default()
+    });
+
+    let second = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, inscription("text/plain", "baz").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default())],
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      events.push(event);
+    }
+
+    assert!(events
+      .windows(2)
+      .all(|pair| pair[0].block_height() <= pair[1].block_height()));
+
+    let first_0 = InscriptionId {
+      txid: first,
+      index: 0,
+    };
+    let first_1 = InscriptionId {
+      txid: first,
+      index: 1,
+    };
+    let second_0 = InscriptionId {
+      txid: second,
+      index: 0,
+    };
+
+    assert_eq!(
+      events
+        .iter()
+        .filter_map(|event| match event {
+          Event::InscriptionCreated {
+            block_height,
+            inscription_id,
+            sequence_number,
+            ..
+          } => Some((*block_height, *inscription_id, *sequence_number)),
+          _ => None,
+        })
+        .collect::<Vec<(u32, InscriptionId, u32)>>(),
+      [(4, first_0, 0), (4, first_1, 1), (4, second_0, 2)],
+    );
+
+    assert_eq!(
+      events
+        .iter()
+        .filter_map(|event| match event {
+          Event::InscriptionTransferred {
+            block_height,
+            inscription_id,
+            ..
+          } => Some((*block_height, *inscription_id)),
+          _ => None,
+        })
+        .collect::<Vec<(u32, InscriptionId)>>(),
+      [(5, first_0), (5, first_1)],
+    );
+  }
+
+  #[test]
+  fn events_are_resent_from_savepoint_after_reorg() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let mut context = Context::builder()
+      .env("NEW_SUPPLY_EVENTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(2);
+
+    context.core.invalidate_tip();
+    context.core.invalidate_tip();
+
+    context.mine_blocks(3);
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      events.push(event);
+    }
+
+    assert_eq!(
+      events
+        .iter()
+        .filter(|event| matches!(event, Event::NewSupply { .. }))
+        .map(Event::block_height)
+        .collect::<Vec<u32>>(),
+      [0, 1, 2, 3, 2, 3, 4],
+    );
+
+    assert_eq!(
+      events
+        .iter()
+        .filter_map(Event::inscription_id)
+        .collect::<Vec<InscriptionId>>(),
+      [InscriptionId { txid, index: 0 }],
+    );
+  }
+
+  #[test]
+  fn inscription_transfer_events_flag_self_transfers() {
+    for index_addresses in [false, true] {
+      let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+      let mut builder = Context::builder().event_sender(event_sender);
+
+      if index_addresses {
+        builder = builder.arg("--index-addresses");
+      }
+
+      let context = builder.build();
+
+      context.mine_blocks(1);
+
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      event_receiver.blocking_recv().unwrap();
+
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 1, 0, Default::default())],
+        p2tr: true,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let Event::InscriptionTransferred { self_transfer, .. } =
+        event_receiver.blocking_recv().unwrap()
+      else {
+        panic!("expected inscription transferred event");
+      };
+
+      assert!(!self_transfer);
+
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(3, 1, 0, Default::default())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let Event::InscriptionTransferred { self_transfer, .. } =
+        event_receiver.blocking_recv().unwrap()
+      else {
+        panic!("expected inscription transferred event");
+      };
+
+      assert!(!self_transfer);
+
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(4, 1, 0, Default::default())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let Event::InscriptionTransferred { self_transfer, .. } =
+        event_receiver.blocking_recv().unwrap()
+      else {
+        panic!("expected inscription transferred event");
+      };
+
+      assert!(self_transfer);
+    }
+  }
+
+  #[test]
+  fn inscription_events_flag_sanctioned_owners() {
+    let sanctioned = ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros());
+
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("sanctioned.txt");
+
+    fs::write(
+      &path,
+      Address::from_script(&sanctioned, Network::Regtest)
+        .unwrap()
+        .to_string(),
+    )
+    .unwrap();
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .env("SANCTIONED_ADDRESSES", path.to_str().unwrap())
+      .event_sender(event_sender)
+      .tempdir(tempdir)
+      .build();
+
+    context.mine_blocks(1);
+
+    let mut sanctioned_counterparty = || match event_receiver.blocking_recv().unwrap() {
+      Event::InscriptionCreated {
+        sanctioned_counterparty,
+        ..
+      }
+      | Event::InscriptionTransferred {
+        sanctioned_counterparty,
+        ..
+      } => sanctioned_counterparty,
+      event => panic!("unexpected event {event:?}"),
+    };
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!sanctioned_counterparty());
+
+    // transferred to sanctioned owner
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(sanctioned_counterparty());
+
+    // transferred from sanctioned owner
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(sanctioned_counterparty());
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!sanctioned_counterparty());
+
+    // created from sanctioned owner's funds
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(5, 0, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(6, 1, 0, inscription("text/plain", "bar").to_witness())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(sanctioned_counterparty());
+  }
+
+  #[test]
+  fn inscription_digest_events() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .env("INSCRIPTION_DIGESTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    let script_pubkey = context.core.tx_by_id(create_txid).output[0]
+      .script_pubkey
+      .clone();
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionCreated { .. }
+    ));
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionDigest {
+        block_height: 2,
+        gained: vec![inscription_id],
+        lost: Vec::new(),
+        rare_sats_gained: Vec::new(),
+        rare_sats_lost: Vec::new(),
+        script_pubkey: script_pubkey.clone(),
+      }
+    );
+
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionTransferred { .. }
+    ));
+
+    let mut digests = vec![
+      event_receiver.blocking_recv().unwrap(),
+      event_receiver.blocking_recv().unwrap(),
+    ];
+
+    digests.sort_by_key(|event| match event {
+      Event::InscriptionDigest { gained, .. } => gained.is_empty(),
+      _ => panic!("expected inscription digest event"),
+    });
+
+    assert_eq!(
+      digests,
+      [
+        Event::InscriptionDigest {
+          block_height: 3,
+          gained: vec![inscription_id],
+          lost: Vec::new(),
+          rare_sats_gained: Vec::new(),
+          rare_sats_lost: Vec::new(),
+          script_pubkey: context.core.tx_by_id(transfer_txid).output[0]
+            .script_pubkey
+            .clone(),
+        },
+        Event::InscriptionDigest {
+          block_height: 3,
+          gained: Vec::new(),
+          lost: vec![inscription_id],
+          rare_sats_gained: Vec::new(),
+          rare_sats_lost: Vec::new(),
+          script_pubkey,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn inscription_digest_events_cancel_out_self_transfers() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .env("INSCRIPTION_DIGESTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionCreated { .. }
+    ));
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionDigest { .. }
+    ));
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      output_values: &[context.core.tx_by_id(create_txid).output[0].value],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionTransferred {
+        self_transfer: true,
+        ..
+      }
+    ));
+
+    context.mine_blocks(1);
+
+    assert!(event_receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn inscription_digest_events_include_rare_sats() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-sats")
+      .env("INSCRIPTION_DIGESTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      if matches!(
+        event,
+        Event::InscriptionDigest {
+          block_height: 2,
+          ..
+        }
+      ) {
+        events.push(event);
+      }
+    }
+
+    let mut expected = vec![
+      Event::InscriptionDigest {
+        block_height: 2,
+        gained: Vec::new(),
+        lost: Vec::new(),
+        rare_sats_gained: vec![Sat(100 * COIN_VALUE)],
+        rare_sats_lost: Vec::new(),
+        script_pubkey: context.core.tx(2, 0).output[0].script_pubkey.clone(),
+      },
+      Event::InscriptionDigest {
+        block_height: 2,
+        gained: Vec::new(),
+        lost: Vec::new(),
+        rare_sats_gained: Vec::new(),
+        rare_sats_lost: vec![Sat(50 * COIN_VALUE)],
+        script_pubkey: context.core.tx(1, 0).output[0].script_pubkey.clone(),
+      },
+      Event::InscriptionDigest {
+        block_height: 2,
+        gained: Vec::new(),
+        lost: Vec::new(),
+        rare_sats_gained: vec![Sat(50 * COIN_VALUE)],
+        rare_sats_lost: Vec::new(),
+        script_pubkey: context.core.tx_by_id(transfer_txid).output[0]
+          .script_pubkey
+          .clone(),
+      },
+    ];
+
+    let script_pubkey = |event: &Event| match event {
+      Event::InscriptionDigest { script_pubkey, .. } => script_pubkey.clone(),
+      _ => panic!("expected inscription digest event"),
+    };
+
+    events.sort_by_key(script_pubkey);
+    expected.sort_by_key(script_pubkey);
+
+    assert_eq!(events, expected);
+  }
+
+  #[test]
+  fn inscription_events_flag_content_type_mismatch() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(2);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("image/jpeg", b"\x89PNG\r\n\x1a\n").to_witness(),
+      )],
+      ..default()
+    });
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription("image/png", b"\x89PNG\r\n\x1a\n").to_witness(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    for expected in [true, false] {
+      let Event::InscriptionCreated {
+        content_type_mismatch,
+        ..
+      } = event_receiver.blocking_recv().unwrap()
+      else {
+        panic!("expected inscription created event");
+      };
+
+      assert_eq!(content_type_mismatch, expected);
+    }
+  }
+
+  #[test]
+  fn inscription_events_include_metadata_traits() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![(
+        Value::Text("traits".into()),
+        Value::Map(vec![(
+          Value::Text("Eyes".into()),
+          Value::Text("Laser".into()),
+        )]),
+      )]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metadata: Some(metadata),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Event::InscriptionCreated { traits, .. } = event_receiver.blocking_recv().unwrap() else {
+      panic!("expected inscription created event");
+    };
+
+    assert_eq!(
+      traits,
+      [("eyes".to_string(), "Laser".to_string())]
+        .into_iter()
+        .collect::<BTreeMap<String, String>>(),
+    );
+  }
+
+  #[test]
+  fn reinscription_events_list_existing_inscriptions() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Event::InscriptionCreated {
+      reinscription_of, ..
+    } = event_receiver.blocking_recv().unwrap()
+    else {
+      panic!("expected inscription created event");
+    };
+
+    assert!(reinscription_of.is_empty());
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(matches!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::InscriptionTransferred { .. }
+    ));
+
+    let Event::InscriptionCreated {
+      reinscription_of, ..
+    } = event_receiver.blocking_recv().unwrap()
+    else {
+      panic!("expected inscription created event");
+    };
+
+    assert_eq!(reinscription_of, [InscriptionId { txid, index: 0 }]);
+  }
+
+  #[test]
+  fn inscription_events_flag_bare_multisig_outputs() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Event::InscriptionCreated { bare_multisig, .. } = event_receiver.blocking_recv().unwrap()
+    else {
+      panic!("expected inscription created event");
+    };
+
+    assert!(!bare_multisig);
+
+    let multisig = script::Builder::new()
+      .push_opcode(opcodes::all::OP_PUSHNUM_1)
+      .push_slice([2; 33])
+      .push_slice([3; 33])
+      .push_opcode(opcodes::all::OP_PUSHNUM_2)
+      .push_opcode(opcodes::all::OP_CHECKMULTISIG)
+      .into_script();
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      op_return: Some(multisig),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let Event::InscriptionTransferred {
+      bare_multisig,
+      old_location,
+      ..
+    } = event_receiver.blocking_recv().unwrap()
+    else {
+      panic!("expected inscription transferred event");
+    };
+
+    assert_eq!(old_location.outpoint.txid, create_txid);
+    assert!(bare_multisig);
+  }
+
+  #[test]
+  fn new_supply_events() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .env("NEW_SUPPLY_EVENTS", "1")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    for height in 0..2 {
+      let Event::NewSupply {
+        block_height,
+        end,
+        miner,
+        notable_sats,
+        start,
+      } = event_receiver.blocking_recv().unwrap()
+      else {
+        panic!("expected new supply event");
+      };
+
+      assert_eq!(block_height, height);
+      assert_eq!(miner, None);
+      assert_eq!(start, Sat(u64::from(height) * 50 * COIN_VALUE));
+      assert_eq!(end, start + 50 * COIN_VALUE);
+      assert_eq!(notable_sats[0], (start, start.charms()));
+      assert!(Charm::Uncommon.is_set(notable_sats[0].1) || height == 0);
+      assert!(notable_sats.windows(2).all(|pair| pair[0].0 < pair[1].0));
+      assert!(notable_sats.iter().skip(1).all(|(sat, charms)| {
+        let digits = sat.n().to_string();
+        *sat < end && *charms == sat.charms() && digits.chars().rev().eq(digits.chars())
+      }));
+    }
+
+    assert!(event_receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn new_supply_notable_sats() {
+    let notable_sats = |height| {
+      let Event::NewSupply { notable_sats, .. } =
+        updater::Updater::new_supply(Height(height), None)
+      else {
+        unreachable!()
+      };
+      notable_sats
+    };
+
+    // all palindromes below 5 billion, of which 0 is also the first sat
+    assert_eq!(notable_sats(0).len(), 149_999);
+
+    // the first sat, and palindromes with first halves 50000 through 99999
+    assert_eq!(notable_sats(1).len(), 50_001);
+
+    // whole coin sats of block nine are nineballs
+    let nineballs = notable_sats(9)
+      .into_iter()
+      .filter(|(sat, charms)| sat.coin() && Charm::Nineball.is_set(*charms))
+      .count();
+
+    assert_eq!(nineballs, 50);
+
+    // plain whole coin sats are left out, while 16 digit palindromes are
+    // far enough apart that only a few fall in a block
+    assert_eq!(
+      notable_sats(880_000),
+      [
+        1981250000000000,
+        1981250000521891,
+        1981250110521891,
+        1981250220521891,
+      ]
+      .map(|n| (Sat(n), Sat(n).charms())),
+    );
+  }
+
+  #[test]
+  fn miner_is_read_from_coinbase_marker() {
+    #[track_caller]
+    fn case(script_sig: &[u8], expected: Option<&str>) {
+      assert_eq!(
+        updater::Updater::miner(Script::from_bytes(script_sig)).as_deref(),
+        expected
+      );
+    }
+
+    case(
+      b"\x03\xa0\xbb\x0d/ViaBTC/Mined by foo/,\xfa\xbe",
+      Some("ViaBTC"),
+    );
+    case(
+      b"\x03\xa0\xbb\x0d\x1c/Foundry USA Pool #dropgold/\x00",
+      Some("Foundry USA Pool #dropgold"),
+    );
+    case(b"\x03\xa0\xbb\x0d//\x01/ F2Pool /", Some("F2Pool"));
+    case(b"\x03\xa0\xbb\x0dMined by AntPool", None);
+    case(b"\x03\xa0\xbb\x0d/unterminated", None);
+    case(b"", None);
+    case(
+      bitcoin::blockdata::constants::genesis_block(Network::Bitcoin).txdata[0].input[0]
+        .script_sig
+        .as_bytes(),
+      None,
+    );
+  }
+
+  #[test]
+  fn rune_cenotaph_event() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .push_opcode(Runestone::MAGIC_NUMBER)
+          .push_opcode(opcodes::all::OP_VERIFY)
+          .into_script(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::RuneCenotaph {
+        block_height: 2,
+        flaw: Some(Flaw::Opcode),
+        txid,
+      }
+    );
+  }
+
   #[test]
   fn rune_event_sender_channel() {
     const RUNE: u128 = 99246114928149462;
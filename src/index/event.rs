@@ -3,26 +3,58 @@ use super::*;
 #[derive(Debug, Clone, PartialEq)]
 pub enum Event {
   InscriptionCreated {
+    bare_multisig: bool,
     block_height: u32,
     charms: u16,
+    content_type_mismatch: bool,
+    exceeds_limits: bool,
+    flagged: bool,
     inscription_id: InscriptionId,
     location: Option<SatPoint>,
     parent_inscription_ids: Vec<InscriptionId>,
+    protocol: Option<Protocol>,
+    reinscription_of: Vec<InscriptionId>,
+    sanctioned_counterparty: bool,
+    sat: Option<Sat>,
     sequence_number: u32,
+    traits: BTreeMap<String, String>,
+  },
+  InscriptionDigest {
+    block_height: u32,
+    gained: Vec<InscriptionId>,
+    lost: Vec<InscriptionId>,
+    rare_sats_gained: Vec<Sat>,
+    rare_sats_lost: Vec<Sat>,
+    script_pubkey: ScriptBuf,
   },
   InscriptionTransferred {
+    bare_multisig: bool,
     block_height: u32,
     inscription_id: InscriptionId,
     new_location: SatPoint,
     old_location: SatPoint,
+    sanctioned_counterparty: bool,
+    self_transfer: bool,
     sequence_number: u32,
   },
+  NewSupply {
+    block_height: u32,
+    end: Sat,
+    miner: Option<String>,
+    notable_sats: Vec<(Sat, u16)>,
+    start: Sat,
+  },
   RuneBurned {
     amount: u128,
     block_height: u32,
     rune_id: RuneId,
     txid: Txid,
   },
+  RuneCenotaph {
+    block_height: u32,
+    flaw: Option<Flaw>,
+    txid: Txid,
+  },
   RuneEtched {
     block_height: u32,
     rune_id: RuneId,
@@ -42,3 +74,79 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+/// Generates `Event::TYPES` and `Event::event_type` from a single table, so
+/// that the match in `event_type` keeps the table complete.
+macro_rules! event_types {
+  ($($variant:ident => $name:literal,)*) => {
+    impl Event {
+      pub(crate) const TYPES: &'static [&'static str] = &[$($name,)*];
+
+      pub fn event_type(&self) -> &'static str {
+        match self {
+          $(Self::$variant { .. } => $name,)*
+        }
+      }
+    }
+  };
+}
+
+event_types! {
+  InscriptionCreated => "inscription_created",
+  InscriptionDigest => "inscription_digest",
+  InscriptionTransferred => "inscription_transferred",
+  NewSupply => "new_supply",
+  RuneBurned => "rune_burned",
+  RuneCenotaph => "rune_cenotaph",
+  RuneEtched => "rune_etched",
+  RuneMinted => "rune_minted",
+  RuneTransferred => "rune_transferred",
+}
+
+impl Event {
+  pub fn block_height(&self) -> u32 {
+    match self {
+      Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionDigest { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::NewSupply { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneCenotaph { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+    }
+  }
+
+  pub fn inscription_id(&self) -> Option<InscriptionId> {
+    match self {
+      Self::InscriptionCreated { inscription_id, .. }
+      | Self::InscriptionTransferred { inscription_id, .. } => Some(*inscription_id),
+      _ => None,
+    }
+  }
+}
+
+/// Sends events to the embedding application, dropping those rejected by the
+/// event filter.
+pub(crate) struct EventSender {
+  pub(crate) filter: Option<EventFilter>,
+  pub(crate) sender: tokio::sync::mpsc::Sender<Event>,
+}
+
+impl EventSender {
+  pub(crate) fn blocking_send(
+    &self,
+    event: Event,
+  ) -> Result<(), tokio::sync::mpsc::error::SendError<Event>> {
+    if self
+      .filter
+      .as_ref()
+      .map_or(true, |filter| filter.matches(&event))
+    {
+      self.sender.blocking_send(event)
+    } else {
+      Ok(())
+    }
+  }
+}
@@ -0,0 +1,448 @@
+use super::*;
+
+/// A boolean expression over events. Events for which it is false are dropped
+/// before being sent. The grammar is:
+///
+/// ```text
+/// filter     = and ("||" and)*
+/// and        = unary ("&&" unary)*
+/// unary      = "!" unary | "(" filter ")" | comparison
+/// comparison = FIELD ("==" | "!=") VALUE | FIELD "in" "(" VALUE ("," VALUE)* ")"
+/// ```
+///
+/// Fields that an event does not have compare as `none` or `false`.
+#[derive(Clone, Debug, DeserializeFromStr, PartialEq, SerializeDisplay)]
+pub struct EventFilter {
+  expression: Expression,
+  source: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expression {
+  And(Box<Expression>, Box<Expression>),
+  In(Field, Vec<String>),
+  Not(Box<Expression>),
+  Or(Box<Expression>, Box<Expression>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+  BareMultisig,
+  EventType,
+  Flagged,
+  Protocol,
+  SanctionedCounterparty,
+  SelfTransfer,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+  And,
+  Close,
+  Comma,
+  Eq,
+  Ne,
+  Not,
+  Open,
+  Or,
+  Word(String),
+}
+
+impl EventFilter {
+  pub(crate) fn matches(&self, event: &Event) -> bool {
+    self.expression.evaluate(event)
+  }
+}
+
+impl Expression {
+  fn evaluate(&self, event: &Event) -> bool {
+    match self {
+      Self::And(a, b) => a.evaluate(event) && b.evaluate(event),
+      Self::In(field, values) => values.contains(&field.value(event)),
+      Self::Not(a) => !a.evaluate(event),
+      Self::Or(a, b) => a.evaluate(event) || b.evaluate(event),
+    }
+  }
+}
+
+impl Field {
+  fn value(self, event: &Event) -> String {
+    match (self, event) {
+      (Self::BareMultisig, Event::InscriptionCreated { bare_multisig, .. })
+      | (Self::BareMultisig, Event::InscriptionTransferred { bare_multisig, .. }) => {
+        bare_multisig.to_string()
+      }
+      (Self::EventType, event) => event.event_type().into(),
+      (Self::Flagged, Event::InscriptionCreated { flagged, .. }) => flagged.to_string(),
+      (
+        Self::Protocol,
+        Event::InscriptionCreated {
+          protocol: Some(protocol),
+          ..
+        },
+      ) => protocol.to_string(),
+      (Self::Protocol, _) => "none".into(),
+      (
+        Self::SanctionedCounterparty,
+        Event::InscriptionCreated {
+          sanctioned_counterparty,
+          ..
+        },
+      )
+      | (
+        Self::SanctionedCounterparty,
+        Event::InscriptionTransferred {
+          sanctioned_counterparty,
+          ..
+        },
+      ) => sanctioned_counterparty.to_string(),
+      (Self::SelfTransfer, Event::InscriptionTransferred { self_transfer, .. }) => {
+        self_transfer.to_string()
+      }
+      _ => "false".into(),
+    }
+  }
+
+  fn values(self) -> Vec<&'static str> {
+    match self {
+      Self::BareMultisig | Self::Flagged | Self::SanctionedCounterparty | Self::SelfTransfer => {
+        vec!["false", "true"]
+      }
+      Self::EventType => Event::TYPES.into(),
+      Self::Protocol => Protocol::ALL
+        .into_iter()
+        .map(Protocol::name)
+        .chain(["none"])
+        .collect(),
+    }
+  }
+}
+
+impl FromStr for Field {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    match s {
+      "bare_multisig" => Ok(Self::BareMultisig),
+      "event_type" => Ok(Self::EventType),
+      "flagged" => Ok(Self::Flagged),
+      "protocol" => Ok(Self::Protocol),
+      "sanctioned_counterparty" => Ok(Self::SanctionedCounterparty),
+      "self_transfer" => Ok(Self::SelfTransfer),
+      _ => Err(anyhow!("unknown event filter field `{s}`")),
+    }
+  }
+}
+
+impl Display for Token {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::And => write!(f, "&&"),
+      Self::Close => write!(f, ")"),
+      Self::Comma => write!(f, ","),
+      Self::Eq => write!(f, "=="),
+      Self::Ne => write!(f, "!="),
+      Self::Not => write!(f, "!"),
+      Self::Open => write!(f, "("),
+      Self::Or => write!(f, "||"),
+      Self::Word(word) => write!(f, "{word}"),
+    }
+  }
+}
+
+struct Parser {
+  position: usize,
+  tokens: Vec<Token>,
+}
+
+impl Parser {
+  fn tokenize(s: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+      let token = match c {
+        c if c.is_whitespace() => continue,
+        '(' => Token::Open,
+        ')' => Token::Close,
+        ',' => Token::Comma,
+        '=' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Eq,
+        '!' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Ne,
+        '!' => Token::Not,
+        '&' if chars.next_if(|(_, c)| *c == '&').is_some() => Token::And,
+        '|' if chars.next_if(|(_, c)| *c == '|').is_some() => Token::Or,
+        c if c.is_ascii_alphanumeric() || c == '_' || c == '-' => {
+          let mut end = i + c.len_utf8();
+          while let Some((j, c)) =
+            chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+          {
+            end = j + c.len_utf8();
+          }
+          Token::Word(s[i..end].into())
+        }
+        c => bail!("unexpected character `{c}` in event filter"),
+      };
+
+      tokens.push(token);
+    }
+
+    Ok(tokens)
+  }
+
+  fn next(&mut self) -> Result<Token> {
+    let token = self
+      .tokens
+      .get(self.position)
+      .cloned()
+      .ok_or_else(|| anyhow!("unexpected end of event filter"))?;
+    self.position += 1;
+    Ok(token)
+  }
+
+  fn accept(&mut self, token: &Token) -> bool {
+    let accepted = self.tokens.get(self.position) == Some(token);
+    if accepted {
+      self.position += 1;
+    }
+    accepted
+  }
+
+  fn expect(&mut self, expected: &Token) -> Result {
+    let token = self.next()?;
+    ensure!(
+      token == *expected,
+      "expected `{expected}` but found `{token}` in event filter"
+    );
+    Ok(())
+  }
+
+  fn or(&mut self) -> Result<Expression> {
+    let mut expression = self.and()?;
+    while self.accept(&Token::Or) {
+      expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+    }
+    Ok(expression)
+  }
+
+  fn and(&mut self) -> Result<Expression> {
+    let mut expression = self.unary()?;
+    while self.accept(&Token::And) {
+      expression = Expression::And(Box::new(expression), Box::new(self.unary()?));
+    }
+    Ok(expression)
+  }
+
+  fn unary(&mut self) -> Result<Expression> {
+    match self.next()? {
+      Token::Not => Ok(Expression::Not(Box::new(self.unary()?))),
+      Token::Open => {
+        let expression = self.or()?;
+        self.expect(&Token::Close)?;
+        Ok(expression)
+      }
+      Token::Word(field) => {
+        let field = field.parse::<Field>()?;
+
+        match self.next()? {
+          Token::Eq => Ok(Expression::In(field, vec![self.value(field)?])),
+          Token::Ne => Ok(Expression::Not(Box::new(Expression::In(
+            field,
+            vec![self.value(field)?],
+          )))),
+          Token::Word(word) if word == "in" => {
+            self.expect(&Token::Open)?;
+            let mut values = vec![self.value(field)?];
+            while self.accept(&Token::Comma) {
+              values.push(self.value(field)?);
+            }
+            self.expect(&Token::Close)?;
+            Ok(Expression::In(field, values))
+          }
+          token => bail!("expected `==`, `!=`, or `in` but found `{token}` in event filter"),
+        }
+      }
+      token => bail!("unexpected `{token}` in event filter"),
+    }
+  }
+
+  fn value(&mut self, field: Field) -> Result<String> {
+    match self.next()? {
+      Token::Word(value) if field.values().contains(&value.as_str()) => Ok(value),
+      token => bail!(
+        "invalid value `{token}` in event filter, expected one of {}",
+        field.values().join(", ")
+      ),
+    }
+  }
+}
+
+impl FromStr for EventFilter {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let mut parser = Parser {
+      position: 0,
+      tokens: Parser::tokenize(s)?,
+    };
+
+    let expression = parser.or()?;
+
+    if let Some(token) = parser.tokens.get(parser.position) {
+      bail!("unexpected `{token}` in event filter");
+    }
+
+    Ok(Self {
+      expression,
+      source: s.into(),
+    })
+  }
+}
+
+impl Display for EventFilter {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.source)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn created(protocol: Option<Protocol>) -> Event {
+    Event::InscriptionCreated {
+      bare_multisig: false,
+      block_height: 0,
+      charms: 0,
+      content_type_mismatch: false,
+      exceeds_limits: false,
+      flagged: true,
+      inscription_id: inscription_id(1),
+      location: None,
+      parent_inscription_ids: Vec::new(),
+      protocol,
+      reinscription_of: Vec::new(),
+      sanctioned_counterparty: false,
+      sat: None,
+      sequence_number: 0,
+      traits: BTreeMap::new(),
+    }
+  }
+
+  fn transferred(self_transfer: bool) -> Event {
+    Event::InscriptionTransferred {
+      bare_multisig: false,
+      block_height: 0,
+      inscription_id: inscription_id(1),
+      new_location: SatPoint::from_str(&format!("{}:0:0", txid(1))).unwrap(),
+      old_location: SatPoint::from_str(&format!("{}:0:0", txid(2))).unwrap(),
+      sanctioned_counterparty: false,
+      self_transfer,
+      sequence_number: 0,
+    }
+  }
+
+  #[track_caller]
+  fn case(filter: &str, event: Event, expected: bool) {
+    assert_eq!(
+      filter.parse::<EventFilter>().unwrap().matches(&event),
+      expected,
+      "{filter}"
+    );
+  }
+
+  #[test]
+  fn comparisons() {
+    case("event_type == inscription_created", created(None), true);
+    case("event_type != inscription_created", created(None), false);
+    case(
+      "event_type in (inscription_transferred, rune_minted)",
+      created(None),
+      false,
+    );
+    case(
+      "event_type in (inscription_transferred, rune_minted)",
+      transferred(false),
+      true,
+    );
+    case("protocol == brc-20", created(Some(Protocol::Brc20)), true);
+    case("protocol == none", created(None), true);
+    case("protocol == none", transferred(false), true);
+    case("flagged == true", created(None), true);
+    case("flagged == true", transferred(false), false);
+    case("self_transfer == true", transferred(true), true);
+    case("self_transfer == true", created(None), false);
+
+    for protocol in Protocol::ALL {
+      case(
+        &format!("protocol == {protocol}"),
+        created(Some(protocol)),
+        true,
+      );
+    }
+  }
+
+  #[test]
+  fn operators() {
+    case(
+      "event_type in (inscription_created, inscription_transferred) && protocol != brc-20",
+      created(Some(Protocol::Brc20)),
+      false,
+    );
+    case(
+      "event_type in (inscription_created, inscription_transferred) && protocol != brc-20",
+      created(Some(Protocol::Tap)),
+      true,
+    );
+    case(
+      "protocol == brc-20 || self_transfer == true && event_type == rune_minted",
+      created(Some(Protocol::Brc20)),
+      true,
+    );
+    case(
+      "(protocol == brc-20 || self_transfer == true) && event_type == rune_minted",
+      created(Some(Protocol::Brc20)),
+      false,
+    );
+    case("!(flagged == true)", created(None), false);
+    case("!!flagged == true", created(None), true);
+  }
+
+  #[test]
+  fn display_round_trips() {
+    let filter = "event_type == rune_minted && protocol != none";
+    assert_eq!(filter.parse::<EventFilter>().unwrap().to_string(), filter);
+    assert_eq!(
+      serde_json::from_str::<EventFilter>(&serde_json::to_string(filter).unwrap()).unwrap(),
+      filter.parse::<EventFilter>().unwrap(),
+    );
+  }
+
+  #[track_caller]
+  fn error(filter: &str, expected: &str) {
+    assert_eq!(
+      filter.parse::<EventFilter>().unwrap_err().to_string(),
+      expected
+    );
+  }
+
+  #[test]
+  fn errors() {
+    error("", "unexpected end of event filter");
+    error("foo == bar", "unknown event filter field `foo`");
+    error(
+      "protocol == brc20",
+      "invalid value `brc20` in event filter, expected one of brc-20, brc-20-self-mint, brc-420, orc-20, tap, none",
+    );
+    error("flagged = true", "unexpected character `=` in event filter");
+    error(
+      "flagged true",
+      "expected `==`, `!=`, or `in` but found `true` in event filter",
+    );
+    error("(flagged == true", "unexpected end of event filter");
+    error("flagged == true)", "unexpected `)` in event filter");
+    error(
+      "event_type in (rune_minted rune_etched)",
+      "expected `)` but found `rune_etched` in event filter",
+    );
+  }
+}
@@ -43,4 +43,120 @@ impl Rtx {
       .map(|header| Header::load(*header.value()).block_hash()),
     )
   }
+
+  pub(crate) fn contains_output(&self, output: &OutPoint) -> Result<bool> {
+    Ok(
+      self
+        .0
+        .open_table(OUTPOINT_TO_VALUE)?
+        .get(&output.store())?
+        .is_some(),
+    )
+  }
+
+  pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<Vec<(u64, u64)>>> {
+    Ok(
+      self
+        .0
+        .open_table(OUTPOINT_TO_SAT_RANGES)?
+        .get(&outpoint.store())?
+        .map(|outpoint| outpoint.value().to_vec())
+        .map(|sat_ranges| {
+          sat_ranges
+            .chunks_exact(11)
+            .map(|chunk| SatRange::load(chunk.try_into().unwrap()))
+            .collect::<Vec<(u64, u64)>>()
+        }),
+    )
+  }
+
+  pub(crate) fn address_outpoints(&self, script_pubkey: &Script) -> Result<Vec<OutPoint>> {
+    self
+      .0
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(script_pubkey.as_bytes())?
+      .map(|result| {
+        result
+          .map(|outpoint| Entry::load(*outpoint.value()))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
+
+  pub(crate) fn transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    self
+      .0
+      .open_table(TRANSACTION_ID_TO_TRANSACTION)?
+      .get(&txid.store())?
+      .map(|transaction| Ok(consensus::encode::deserialize(transaction.value())?))
+      .transpose()
+  }
+
+  pub(crate) fn inscription_entry(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<InscriptionEntry>> {
+    let Some(sequence_number) = self
+      .0
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      self
+        .0
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .get(sequence_number)?
+        .map(|value| InscriptionEntry::load(value.value())),
+    )
+  }
+
+  pub(crate) fn inscriptions_on_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    Index::inscriptions_on_output(
+      &self.0.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?,
+      &self.0.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?,
+      outpoint,
+    )
+  }
+
+  pub(crate) fn rune_balances_for_outpoint(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SpacedRune, Pile)>> {
+    let outpoint_to_balances = self.0.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let id_to_rune_entries = self.0.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(Vec::new());
+    };
+
+    let balances_buffer = balances.value();
+
+    let mut balances = Vec::new();
+    let mut i = 0;
+    while i < balances_buffer.len() {
+      let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+      i += length;
+
+      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+
+      balances.push((
+        entry.spaced_rune,
+        Pile {
+          amount,
+          divisibility: entry.divisibility,
+          symbol: entry.symbol,
+        },
+      ));
+    }
+
+    Ok(balances)
+  }
 }
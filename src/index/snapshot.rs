@@ -0,0 +1,316 @@
+use {
+  super::*,
+  brotli::{enc::writer::CompressorWriter, Decompressor},
+  std::io::Read,
+};
+
+const MAGIC: &[u8; 8] = b"ordsnap\0";
+
+// bump when the snapshot layout changes
+const VERSION: u32 = 1;
+
+// brotli quality and window size, favoring speed since indices are large
+const QUALITY: u32 = 4;
+const WINDOW: u32 = 22;
+
+const BUFFER_SIZE: usize = 1 << 20;
+
+// headers are a few dozen bytes of JSON, so anything larger is corrupt
+const MAX_HEADER_SIZE: usize = 1 << 16;
+
+/// Describes the index contained in a snapshot. Written uncompressed and
+/// length-prefixed after the magic bytes, and followed by the brotli
+/// compressed index file.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Header {
+  pub chain: Chain,
+  pub height: Option<u32>,
+  pub schema: u64,
+  pub version: u32,
+}
+
+impl Index {
+  /// Write a snapshot of the index at `settings.index()` to `writer`. The
+  /// index must not be open in another process.
+  pub(crate) fn export_snapshot(settings: &Settings, writer: impl Write) -> Result<Header> {
+    let path = settings.index();
+
+    let header = {
+      let database = Database::builder().open(path).with_context(|| {
+        format!(
+          "failed to open index at `{}`, is it in use by another process?",
+          path.display()
+        )
+      })?;
+
+      let rtx = database.begin_read()?;
+
+      let schema = rtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::Schema.key())?
+        .map(|x| x.value())
+        .unwrap_or(0);
+
+      ensure!(
+        schema == SCHEMA_VERSION,
+        "index at `{}` has schema {schema}, ord schema {SCHEMA_VERSION}",
+        path.display()
+      );
+
+      Header {
+        chain: settings.chain(),
+        height: rtx::Rtx(rtx).block_height()?.map(|height| height.n()),
+        schema,
+        version: VERSION,
+      }
+    };
+
+    // the database must be closed before copying, since an open database is
+    // marked as needing recovery
+    let mut index = fs::File::open(path)?;
+
+    let mut writer = BufWriter::with_capacity(BUFFER_SIZE, writer);
+
+    let header_json = serde_json::to_vec(&header)?;
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&u32::try_from(header_json.len())?.to_le_bytes())?;
+    writer.write_all(&header_json)?;
+
+    let mut compressor = CompressorWriter::new(writer, BUFFER_SIZE, QUALITY, WINDOW);
+
+    io::copy(&mut index, &mut compressor)?;
+
+    compressor.into_inner().flush()?;
+
+    Ok(header)
+  }
+
+  /// Read a snapshot from `reader` into a new index at `settings.index()`.
+  pub(crate) fn import_snapshot(settings: &Settings, reader: impl Read) -> Result<Header> {
+    let path = settings.index();
+
+    ensure!(
+      !path.exists(),
+      "index already exists at `{}`, delete it before importing a snapshot",
+      path.display()
+    );
+
+    let mut reader = io::BufReader::with_capacity(BUFFER_SIZE, reader);
+
+    let mut magic = [0; MAGIC.len()];
+    reader
+      .read_exact(&mut magic)
+      .context("failed to read snapshot header")?;
+    ensure!(&magic == MAGIC, "not an ord index snapshot");
+
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+
+    let len = u32::from_le_bytes(len).into_usize();
+
+    ensure!(len <= MAX_HEADER_SIZE, "invalid snapshot header");
+
+    let mut header_json = vec![0; len];
+    reader.read_exact(&mut header_json)?;
+
+    let header = serde_json::from_slice::<Header>(&header_json)
+      .context("failed to deserialize snapshot header")?;
+
+    ensure!(
+      header.version == VERSION,
+      "snapshot version {} is not supported, ord snapshot version {VERSION}",
+      header.version,
+    );
+
+    ensure!(
+      header.schema == SCHEMA_VERSION,
+      "snapshot has index schema {}, ord schema {SCHEMA_VERSION}",
+      header.schema,
+    );
+
+    ensure!(
+      header.chain == settings.chain(),
+      "snapshot is for {}, not {}",
+      header.chain,
+      settings.chain(),
+    );
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)
+        .with_context(|| format!("failed to create data dir `{}`", parent.display()))?;
+    }
+
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let result = (|| {
+      io::copy(
+        &mut Decompressor::new(reader, BUFFER_SIZE),
+        &mut fs::File::create(&partial)?,
+      )?;
+
+      let height = rtx::Rtx(Database::builder().open(&partial)?.begin_read()?)
+        .block_height()?
+        .map(|height| height.n());
+
+      ensure!(
+        height == header.height,
+        "snapshot index is at height {height:?}, but snapshot header says {:?}",
+        header.height,
+      );
+
+      fs::rename(&partial, path)?;
+
+      Ok(())
+    })();
+
+    if result.is_err() {
+      fs::remove_file(&partial).ok();
+    }
+
+    result.map(|()| header)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn export_and_import_round_trip() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(2);
+
+    let Context {
+      index,
+      tempdir,
+      core: _core,
+    } = context;
+
+    drop(index);
+
+    let settings = Settings::from_options(Options {
+      regtest: true,
+      index: Some(tempdir.path().join("regtest/index.redb")),
+      ..default()
+    })
+    .or_defaults()
+    .unwrap();
+
+    let mut snapshot = Vec::new();
+
+    assert_eq!(
+      Index::export_snapshot(&settings, &mut snapshot).unwrap(),
+      Header {
+        chain: Chain::Regtest,
+        height: Some(2),
+        schema: SCHEMA_VERSION,
+        version: VERSION,
+      }
+    );
+
+    assert!(Index::import_snapshot(&settings, snapshot.as_slice())
+      .unwrap_err()
+      .to_string()
+      .starts_with("index already exists"));
+
+    let imported = tempdir.path().join("imported.redb");
+
+    let settings = Settings::from_options(Options {
+      regtest: true,
+      index: Some(imported.clone()),
+      ..default()
+    })
+    .or_defaults()
+    .unwrap();
+
+    assert_eq!(
+      Index::import_snapshot(&settings, snapshot.as_slice())
+        .unwrap()
+        .height,
+      Some(2),
+    );
+
+    assert_eq!(
+      rtx::Rtx(Database::open(&imported).unwrap().begin_read().unwrap())
+        .block_height()
+        .unwrap(),
+      Some(Height(2)),
+    );
+  }
+
+  #[test]
+  fn import_rejects_invalid_snapshots() {
+    let tempdir = TempDir::new().unwrap();
+
+    let settings = Settings::from_options(Options {
+      regtest: true,
+      index: Some(tempdir.path().join("index.redb")),
+      ..default()
+    })
+    .or_defaults()
+    .unwrap();
+
+    assert_eq!(
+      Index::import_snapshot(&settings, b"foo".as_slice())
+        .unwrap_err()
+        .to_string(),
+      "failed to read snapshot header",
+    );
+
+    let mut oversized = MAGIC.to_vec();
+    oversized.extend(u32::MAX.to_le_bytes());
+
+    assert_eq!(
+      Index::import_snapshot(&settings, oversized.as_slice())
+        .unwrap_err()
+        .to_string(),
+      "invalid snapshot header",
+    );
+
+    let snapshot = |header: Header| {
+      let header = serde_json::to_vec(&header).unwrap();
+      let mut snapshot = MAGIC.to_vec();
+      snapshot.extend(u32::try_from(header.len()).unwrap().to_le_bytes());
+      snapshot.extend(header);
+      snapshot
+    };
+
+    assert_eq!(
+      Index::import_snapshot(
+        &settings,
+        snapshot(Header {
+          chain: Chain::Regtest,
+          height: None,
+          schema: SCHEMA_VERSION,
+          version: VERSION + 1,
+        })
+        .as_slice()
+      )
+      .unwrap_err()
+      .to_string(),
+      format!("snapshot version 2 is not supported, ord snapshot version {VERSION}"),
+    );
+
+    assert_eq!(
+      Index::import_snapshot(
+        &settings,
+        snapshot(Header {
+          chain: Chain::Mainnet,
+          height: None,
+          schema: SCHEMA_VERSION,
+          version: VERSION,
+        })
+        .as_slice()
+      )
+      .unwrap_err()
+      .to_string(),
+      "snapshot is for mainnet, not regtest",
+    );
+
+    assert!(!tempdir.path().join("index.redb").exists());
+  }
+}
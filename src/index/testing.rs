@@ -3,6 +3,7 @@ use {super::*, bitcoin::script::PushBytes, std::ffi::OsString, tempfile::TempDir
 pub(crate) struct ContextBuilder {
   args: Vec<OsString>,
   chain: Chain,
+  env: BTreeMap<String, String>,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   tempdir: Option<TempDir>,
 }
@@ -32,7 +33,10 @@ impl ContextBuilder {
 
     let options = Options::try_parse_from(command.into_iter().chain(self.args)).unwrap();
     let index = Index::open_with_event_sender(
-      &Settings::from_options(options).or_defaults().unwrap(),
+      &Settings::from_options(options)
+        .or(Settings::from_env(self.env)?)
+        .or_defaults()
+        .unwrap(),
       self.event_sender,
     )?;
     index.update().unwrap();
@@ -59,6 +63,11 @@ impl ContextBuilder {
     self
   }
 
+  pub(crate) fn env(mut self, key: &str, value: &str) -> Self {
+    self.env.insert(key.into(), value.into());
+    self
+  }
+
   pub(crate) fn tempdir(mut self, tempdir: TempDir) -> Self {
     self.tempdir = Some(tempdir);
     self
@@ -82,6 +91,7 @@ impl Context {
     ContextBuilder {
       args: Vec::new(),
       chain: Chain::Regtest,
+      env: BTreeMap::new(),
       event_sender: None,
       tempdir: None,
     }
@@ -1,8 +1,10 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
   super::{fetcher::Fetcher, *},
+  crate::sat_pattern::Palindromes,
+  bitcoin::blockdata::constants::COIN_VALUE,
   futures::future::try_join_all,
-  std::sync::mpsc,
+  std::{iter, sync::mpsc},
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
 };
 
@@ -71,16 +73,20 @@ impl<'index> Updater<'index> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(
+      self.index,
+      self.height,
+      self.index.index_sats || self.index.index_addresses,
+    )?;
 
-    let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(&self.index.settings)?;
+    let (mut outpoint_sender, mut output_receiver) = Self::spawn_fetcher(&self.index.settings)?;
 
     let mut uncommitted = 0;
     let mut value_cache = HashMap::new();
     while let Ok(block) = rx.recv() {
       self.index_block(
         &mut outpoint_sender,
-        &mut value_receiver,
+        &mut output_receiver,
         &mut wtx,
         block,
         &mut value_cache,
@@ -153,7 +159,7 @@ impl<'index> Updater<'index> {
   fn fetch_blocks_from(
     index: &Index,
     mut height: u32,
-    index_sats: bool,
+    full_blocks: bool,
   ) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
@@ -170,7 +176,7 @@ impl<'index> Updater<'index> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match Self::get_block_with_retries(&client, height, full_blocks, first_inscription_height) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -192,7 +198,7 @@ impl<'index> Updater<'index> {
   fn get_block_with_retries(
     client: &Client,
     height: u32,
-    index_sats: bool,
+    full_blocks: bool,
     first_inscription_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
@@ -203,7 +209,7 @@ impl<'index> Updater<'index> {
         .and_then(|option| {
           option
             .map(|hash| {
-              if index_sats || height >= first_inscription_height {
+              if full_blocks || height >= first_inscription_height {
                 Ok(client.get_block(&hash)?)
               } else {
                 Ok(Block {
@@ -235,14 +241,14 @@ impl<'index> Updater<'index> {
     }
   }
 
-  fn spawn_fetcher(settings: &Settings) -> Result<(Sender<OutPoint>, Receiver<u64>)> {
+  fn spawn_fetcher(settings: &Settings) -> Result<(Sender<OutPoint>, Receiver<TxOut>)> {
     let fetcher = Fetcher::new(settings)?;
 
     // Not sure if any block has more than 20k inputs, but none so far after first inscription block
     const CHANNEL_BUFFER_SIZE: usize = 20_000;
     let (outpoint_sender, mut outpoint_receiver) =
       tokio::sync::mpsc::channel::<OutPoint>(CHANNEL_BUFFER_SIZE);
-    let (value_sender, value_receiver) = tokio::sync::mpsc::channel::<u64>(CHANNEL_BUFFER_SIZE);
+    let (output_sender, output_receiver) = tokio::sync::mpsc::channel::<TxOut>(CHANNEL_BUFFER_SIZE);
 
     // Batch 2048 missing inputs at a time. Arbitrarily chosen for now, maybe higher or lower can be faster?
     // Did rudimentary benchmarks with 1024 and 4096 and time was roughly the same.
@@ -287,13 +293,13 @@ impl<'index> Updater<'index> {
               return;
             }
           };
-          // Send all tx output values back in order
+          // Send all tx outputs back in order
           for (i, tx) in txs.iter().flatten().enumerate() {
-            let Ok(_) = value_sender
-              .send(tx.output[usize::try_from(outpoints[i].vout).unwrap()].value)
+            let Ok(_) = output_sender
+              .send(tx.output[usize::try_from(outpoints[i].vout).unwrap()].clone())
               .await
             else {
-              log::error!("Value channel closed unexpectedly");
+              log::error!("Output channel closed unexpectedly");
               return;
             };
           }
@@ -301,13 +307,13 @@ impl<'index> Updater<'index> {
       })
     });
 
-    Ok((outpoint_sender, value_receiver))
+    Ok((outpoint_sender, output_receiver))
   }
 
   fn index_block(
     &mut self,
     outpoint_sender: &mut Sender<OutPoint>,
-    value_receiver: &mut Receiver<u64>,
+    output_receiver: &mut Receiver<TxOut>,
     wtx: &mut WriteTransaction,
     block: BlockData,
     value_cache: &mut HashMap<OutPoint, u64>,
@@ -325,24 +331,57 @@ impl<'index> Updater<'index> {
       block.txdata.len()
     );
 
-    // If value_receiver still has values something went wrong with the last block
+    // If output_receiver still has outputs something went wrong with the last block
     // Could be an assert, shouldn't recover from this and commit the last block
-    let Err(TryRecvError::Empty) = value_receiver.try_recv() else {
+    let Err(TryRecvError::Empty) = output_receiver.try_recv() else {
       return Err(anyhow!("Previous block did not consume all input values"));
     };
 
+    if let Some(sender) = &self.index.event_sender {
+      if self.index.settings.new_supply_events() && Height(self.height).subsidy() > 0 {
+        sender.blocking_send(Self::new_supply(
+          Height(self.height),
+          block
+            .txdata
+            .first()
+            .and_then(|(coinbase, _)| coinbase.input.first())
+            .and_then(|input| Self::miner(&input.script_sig)),
+        ))?;
+      }
+    }
+
     let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
+    let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
 
     let index_inscriptions = self.height >= self.index.first_inscription_height
       && self.index.settings.index_inscriptions();
 
+    let block_transactions = block
+      .txdata
+      .iter()
+      .map(|(tx, txid)| (*txid, tx))
+      .collect::<HashMap<Txid, &Transaction>>();
+
+    let mut fetched_outpoints = HashSet::new();
+
     if index_inscriptions {
+      // Events need the script pubkey of the output an inscription is spent
+      // from, which we only have on hand if addresses are indexed
+      let fetch_script_pubkeys = self.index.event_sender.is_some() && !self.index.index_addresses;
+
+      let screen_sanctioned = !self.index.settings.sanctioned_scripts().is_empty();
+
+      // Digests need the script pubkey of outputs rare sats are spent from
+      let outpoint_to_sat_ranges = if fetch_script_pubkeys
+        && self.index.index_sats
+        && self.index.settings.inscription_digests()
+      {
+        Some(wtx.open_table(OUTPOINT_TO_SAT_RANGES)?)
+      } else {
+        None
+      };
+
       // Send all missing input outpoints to be fetched right away
-      let txids = block
-        .txdata
-        .iter()
-        .map(|(_, txid)| txid)
-        .collect::<HashSet<_>>();
       for (tx, _) in &block.txdata {
         for input in &tx.input {
           let prev_output = input.previous_output;
@@ -352,24 +391,37 @@ impl<'index> Updater<'index> {
           }
           // We don't need input values from txs earlier in the block, since they'll be added to value_cache
           // when the tx is indexed
-          if txids.contains(&prev_output.txid) {
+          if block_transactions.contains_key(&prev_output.txid) {
             continue;
           }
+          // We need the script pubkey of outputs holding or creating inscriptions, even if we already know their
+          // value
+          let script_pubkey_needed = fetch_script_pubkeys
+            && (screen_sanctioned && input.witness.tapscript().is_some()
+              || Self::holds_inscriptions(&satpoint_to_sequence_number, prev_output)?
+              || self.holds_rare_sats(outpoint_to_sat_ranges.as_ref(), prev_output)?);
           // We don't need input values we already have in our value_cache from earlier blocks
-          if value_cache.contains_key(&prev_output) {
+          if !script_pubkey_needed && value_cache.contains_key(&prev_output) {
             continue;
           }
           // We don't need input values we already have in our outpoint_to_value table from earlier blocks that
           // were committed to db already
-          if outpoint_to_value.get(&prev_output.store())?.is_some() {
+          if !script_pubkey_needed && outpoint_to_value.get(&prev_output.store())?.is_some() {
             continue;
           }
           // We don't know the value of this tx input. Send this outpoint to background thread to be fetched
           outpoint_sender.blocking_send(prev_output)?;
+          fetched_outpoints.insert(prev_output);
         }
       }
     }
 
+    let outpoint_to_script_pubkey = if self.index.index_addresses {
+      Some(wtx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?)
+    } else {
+      None
+    };
+
     let mut content_type_to_count = wtx.open_table(CONTENT_TYPE_TO_COUNT)?;
     let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
@@ -379,11 +431,12 @@ impl<'index> Updater<'index> {
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-    let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_and_height_to_satpoint =
+      wtx.open_table(SEQUENCE_NUMBER_AND_HEIGHT_TO_SATPOINT)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
@@ -418,32 +471,43 @@ impl<'index> Updater<'index> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      block_transactions: &block_transactions,
       chain: self.index.settings.chain(),
       content_type_to_count: &mut content_type_to_count,
       cursed_inscription_count,
+      digests: self
+        .index
+        .settings
+        .inscription_digests()
+        .then(BTreeMap::new),
       event_sender: self.index.event_sender.as_ref(),
+      fetched_outpoints: &fetched_outpoints,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: &mut inscription_id_to_sequence_number,
+      index_inscription_history: self.index.index_inscription_history,
       index_transactions: self.index.index_transactions,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
       next_sequence_number,
+      outpoint_to_script_pubkey: outpoint_to_script_pubkey.as_ref(),
       outpoint_to_value: &mut outpoint_to_value,
       reward: Height(self.height).subsidy(),
+      settings: &self.index.settings,
       sat_to_sequence_number: &mut sat_to_sequence_number,
       satpoint_to_sequence_number: &mut satpoint_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_and_height_to_satpoint: &mut sequence_number_and_height_to_satpoint,
       sequence_number_to_satpoint: &mut sequence_number_to_satpoint,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
       unbound_inscriptions,
+      output_receiver,
       value_cache,
-      value_receiver,
     };
 
     if self.index.index_sats {
@@ -525,7 +589,7 @@ impl<'index> Updater<'index> {
           .unwrap_or_default();
 
         for (start, end) in coinbase_inputs {
-          if !Sat(start).common() {
+          if self.index.tracks_sat(Sat(start)) {
             sat_to_satpoint.insert(
               &start,
               &SatPoint {
@@ -550,6 +614,8 @@ impl<'index> Updater<'index> {
     }
 
     if index_inscriptions {
+      inscription_updater.send_digests()?;
+
       height_to_last_sequence_number
         .insert(&self.height, inscription_updater.next_sequence_number)?;
     }
@@ -578,6 +644,35 @@ impl<'index> Updater<'index> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    if let Some(mut outpoint_to_script_pubkey) = outpoint_to_script_pubkey {
+      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      for (tx, txid) in &block.txdata {
+        for input in &tx.input {
+          let outpoint = input.previous_output.store();
+
+          if let Some(script_pubkey) = outpoint_to_script_pubkey.remove(&outpoint)? {
+            script_pubkey_to_outpoint.remove(script_pubkey.value(), &outpoint)?;
+          }
+        }
+
+        for (vout, output) in tx.output.iter().enumerate() {
+          if output.script_pubkey.is_op_return() {
+            continue;
+          }
+
+          let outpoint = OutPoint {
+            txid: *txid,
+            vout: vout.try_into().unwrap(),
+          }
+          .store();
+
+          outpoint_to_script_pubkey.insert(&outpoint, output.script_pubkey.as_bytes())?;
+          script_pubkey_to_outpoint.insert(output.script_pubkey.as_bytes(), &outpoint)?;
+        }
+      }
+    }
+
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
@@ -652,13 +747,14 @@ impl<'index> Updater<'index> {
       };
       let mut sats = Vec::new();
 
+      // zero-value outputs, such as anchors, are assigned no sat ranges
       let mut remaining = output.value;
       while remaining > 0 {
         let range = input_sat_ranges
           .pop_front()
           .ok_or_else(|| anyhow!("insufficient inputs for transaction outputs"))?;
 
-        if !Sat(range.0).common() {
+        if self.index.tracks_sat(Sat(range.0)) {
           sat_to_satpoint.insert(
             &range.0,
             &SatPoint {
@@ -696,6 +792,111 @@ impl<'index> Updater<'index> {
     Ok(())
   }
 
+  fn holds_inscriptions(
+    satpoint_to_sequence_number: &MultimapTable<&SatPointValue, u32>,
+    outpoint: OutPoint,
+  ) -> Result<bool> {
+    let start = SatPoint {
+      outpoint,
+      offset: 0,
+    }
+    .store();
+
+    let end = SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    }
+    .store();
+
+    Ok(
+      satpoint_to_sequence_number
+        .range::<&[u8; 44]>(&start..=&end)?
+        .next()
+        .is_some(),
+    )
+  }
+
+  fn holds_rare_sats(
+    &self,
+    outpoint_to_sat_ranges: Option<&Table<&OutPointValue, &[u8]>>,
+    outpoint: OutPoint,
+  ) -> Result<bool> {
+    let Some(outpoint_to_sat_ranges) = outpoint_to_sat_ranges else {
+      return Ok(false);
+    };
+
+    let key = outpoint.store();
+
+    let has_rare_sats = |sat_ranges: &[u8]| {
+      sat_ranges
+        .chunks_exact(11)
+        .any(|chunk| !Sat(SatRange::load(chunk.try_into().unwrap()).0).common())
+    };
+
+    Ok(match self.range_cache.get(&key) {
+      Some(sat_ranges) => has_rare_sats(sat_ranges),
+      None => outpoint_to_sat_ranges
+        .get(&key)?
+        .is_some_and(|sat_ranges| has_rare_sats(sat_ranges.value())),
+    })
+  }
+
+  /// Returns the pool name from a `/<POOL>/` marker at the start of a run of
+  /// printable characters in a coinbase script sig, the convention used by
+  /// most mining pools.
+  pub(super) fn miner(script_sig: &Script) -> Option<String> {
+    for run in script_sig
+      .as_bytes()
+      .split(|byte| !(byte.is_ascii_graphic() || *byte == b' '))
+    {
+      if !run.starts_with(b"/") {
+        continue;
+      }
+
+      let segments = run.split(|byte| *byte == b'/').collect::<Vec<&[u8]>>();
+
+      for segment in segments.iter().take(segments.len() - 1).skip(1) {
+        let name = std::str::from_utf8(segment).ok()?.trim();
+
+        if !name.is_empty() {
+          return Some(name.into());
+        }
+      }
+    }
+
+    None
+  }
+
+  pub(super) fn new_supply(height: Height, miner: Option<String>) -> Event {
+    let start = height.starting_sat();
+    let end = start + height.subsidy();
+
+    // palindromes and the first sat are notable in themselves, while whole
+    // coin sats are only notable if they have another charm
+    let notable_sats = iter::once(start)
+      .chain(
+        Palindromes::new(start.n().into()..end.n().into())
+          .map(|palindrome| Sat(palindrome.try_into().unwrap())),
+      )
+      .chain(
+        (start.n().div_ceil(COIN_VALUE)..end.n().div_ceil(COIN_VALUE))
+          .map(|coin| Sat(coin * COIN_VALUE))
+          .filter(|coin| Charm::Coin.unset(coin.charms()) != 0),
+      )
+      .collect::<BTreeSet<Sat>>()
+      .into_iter()
+      .map(|sat| (sat, sat.charms()))
+      .collect::<Vec<(Sat, u16)>>();
+
+    Event::NewSupply {
+      block_height: height.n(),
+      end,
+      miner,
+      notable_sats,
+      start,
+    }
+  }
+
   fn commit(&mut self, wtx: WriteTransaction, value_cache: HashMap<OutPoint, u64>) -> Result {
     log::info!(
       "Committing at block height {}, {} outputs traversed, {} in map, {} cached",
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, bitcoin::blockdata::opcodes};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Curse {
@@ -13,6 +13,14 @@ enum Curse {
   UnrecognizedEvenField,
 }
 
+#[derive(Debug, Default)]
+pub(super) struct Digest {
+  gained: Vec<InscriptionId>,
+  lost: Vec<InscriptionId>,
+  rare_sats_gained: Vec<Sat>,
+  rare_sats_lost: Vec<Sat>,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct Flotsam {
   inscription_id: InscriptionId,
@@ -23,48 +31,65 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    content_type_mismatch: bool,
     cursed: bool,
+    exceeds_limits: bool,
     fee: u64,
+    flagged: bool,
     hidden: bool,
     parents: Vec<InscriptionId>,
     pointer: Option<u64>,
+    protocol: Option<Protocol>,
     reinscription: bool,
+    reinscription_of: Vec<InscriptionId>,
+    sanctioned_input: bool,
+    traits: BTreeMap<String, String>,
     unbound: bool,
     vindicated: bool,
   },
   Old {
     old_satpoint: SatPoint,
+    old_script_pubkey: Option<ScriptBuf>,
   },
 }
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) block_transactions: &'a HashMap<Txid, &'a Transaction>,
   pub(super) chain: Chain,
   pub(super) content_type_to_count: &'a mut Table<'tx, Option<&'static [u8]>, u64>,
   pub(super) cursed_inscription_count: u64,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) digests: Option<BTreeMap<ScriptBuf, Digest>>,
+  pub(super) event_sender: Option<&'a EventSender>,
+  pub(super) fetched_outpoints: &'a HashSet<OutPoint>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
   pub(super) home_inscriptions: &'a mut Table<'tx, u32, InscriptionIdValue>,
   pub(super) id_to_sequence_number: &'a mut Table<'tx, InscriptionIdValue, u32>,
+  pub(super) index_inscription_history: bool,
   pub(super) index_transactions: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'tx, i32, u32>,
   pub(super) lost_sats: u64,
   pub(super) next_sequence_number: u32,
+  pub(super) outpoint_to_script_pubkey:
+    Option<&'a Table<'tx, &'static OutPointValue, &'static [u8]>>,
   pub(super) outpoint_to_value: &'a mut Table<'tx, &'static OutPointValue, u64>,
   pub(super) reward: u64,
+  pub(super) settings: &'a Settings,
   pub(super) transaction_buffer: Vec<u8>,
   pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) satpoint_to_sequence_number: &'a mut MultimapTable<'tx, &'static SatPointValue, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_and_height_to_satpoint:
+    &'a mut Table<'tx, (u32, u32), &'static SatPointValue>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'tx, u32, &'static SatPointValue>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
+  pub(super) output_receiver: &'a mut Receiver<TxOut>,
   pub(super) value_cache: &'a mut HashMap<OutPoint, u64>,
-  pub(super) value_receiver: &'a mut Receiver<u64>,
 }
 
 impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
@@ -81,10 +106,28 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     let mut total_input_value = 0;
     let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
 
+    let bare_multisig = tx
+      .output
+      .iter()
+      .any(|tx_out| Self::is_bare_multisig(&tx_out.script_pubkey));
+
     let envelopes = ParsedEnvelope::from_transaction(tx);
     let inscriptions = !envelopes.is_empty();
     let mut envelopes = envelopes.into_iter().peekable();
 
+    // rare sats are always the first sat of a range, so only range starts are checked
+    let rare_sats = input_sat_ranges
+      .filter(|_| self.digests.is_some())
+      .into_iter()
+      .flatten()
+      .scan(0, |offset, (start, end)| {
+        let range_offset = *offset;
+        *offset += end - start;
+        Some((range_offset, Sat(*start)))
+      })
+      .filter(|(_, sat)| !sat.common())
+      .collect::<Vec<(u64, Sat)>>();
+
     for (input_index, tx_in) in tx.input.iter().enumerate() {
       // skip subsidy since no inscriptions possible
       if tx_in.previous_output.is_null() {
@@ -92,6 +135,44 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         continue;
       }
 
+      // multi-level cache for UTXO set to get to the input amount
+      let cached_value = if let Some(value) = self.value_cache.remove(&tx_in.previous_output) {
+        Some(value)
+      } else {
+        self
+          .outpoint_to_value
+          .remove(&tx_in.previous_output.store())?
+          .map(|value| value.value())
+      };
+
+      // fetched outputs come with their script pubkey, even if their value was cached
+      let (current_input_value, input_script_pubkey) =
+        if self.fetched_outpoints.contains(&tx_in.previous_output) {
+          let tx_out = self.output_receiver.blocking_recv().ok_or_else(|| {
+            anyhow!(
+              "failed to get transaction for {}",
+              tx_in.previous_output.txid
+            )
+          })?;
+
+          (tx_out.value, Some(tx_out.script_pubkey))
+        } else {
+          let value = cached_value.ok_or_else(|| {
+            anyhow!(
+              "failed to get transaction for {}",
+              tx_in.previous_output.txid
+            )
+          })?;
+
+          let script_pubkey = if self.event_sender.is_some() {
+            self.input_script_pubkey(tx_in.previous_output)?
+          } else {
+            None
+          };
+
+          (value, script_pubkey)
+        };
+
       // find existing inscriptions on input (transfers of inscriptions)
       for (old_satpoint, inscription_id) in Index::inscriptions_on_output(
         self.satpoint_to_sequence_number,
@@ -102,7 +183,10 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         floating_inscriptions.push(Flotsam {
           offset,
           inscription_id,
-          origin: Origin::Old { old_satpoint },
+          origin: Origin::Old {
+            old_satpoint,
+            old_script_pubkey: input_script_pubkey.clone(),
+          },
         });
 
         inscribed_offsets
@@ -113,23 +197,20 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
 
       let offset = total_input_value;
 
-      // multi-level cache for UTXO set to get to the input amount
-      let current_input_value = if let Some(value) = self.value_cache.remove(&tx_in.previous_output)
+      if let Some((digests, input_script_pubkey)) =
+        self.digests.as_mut().zip(input_script_pubkey.as_ref())
       {
-        value
-      } else if let Some(value) = self
-        .outpoint_to_value
-        .remove(&tx_in.previous_output.store())?
-      {
-        value.value()
-      } else {
-        self.value_receiver.blocking_recv().ok_or_else(|| {
-          anyhow!(
-            "failed to get transaction for {}",
-            tx_in.previous_output.txid
-          )
-        })?
-      };
+        for (_, sat) in rare_sats
+          .iter()
+          .filter(|(sat_offset, _)| (offset..offset + current_input_value).contains(sat_offset))
+        {
+          digests
+            .entry(input_script_pubkey.clone())
+            .or_default()
+            .rare_sats_lost
+            .push(*sat);
+        }
+      }
 
       total_input_value += current_input_value;
 
@@ -210,12 +291,25 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           inscription_id,
           offset,
           origin: Origin::New {
+            content_type_mismatch: inscription.payload.content_type_mismatch(),
             cursed: curse.is_some() && !jubilant,
+            exceeds_limits: self.settings.exceeds_limits(&inscription.payload),
             fee: 0,
+            flagged: self
+              .settings
+              .is_flagged(inscription_id, &inscription.payload),
             hidden: inscription.payload.hidden(),
             parents: inscription.payload.parents(),
             pointer: inscription.payload.pointer(),
+            protocol: inscription.payload.protocol(),
             reinscription: inscribed_offsets.get(&offset).is_some(),
+            reinscription_of: floating_inscriptions
+              .iter()
+              .filter(|flotsam| flotsam.offset == offset)
+              .map(|flotsam| flotsam.inscription_id)
+              .collect(),
+            sanctioned_input: self.is_sanctioned(input_script_pubkey.as_deref()),
+            traits: inscription.payload.traits(),
             unbound: current_input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
               || inscription.payload.unrecognized_even_field,
@@ -288,12 +382,23 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     floating_inscriptions.sort_by_key(|flotsam| flotsam.offset);
     let mut inscriptions = floating_inscriptions.into_iter().peekable();
 
+    let mut rare_sats = rare_sats.into_iter().peekable();
     let mut range_to_vout = BTreeMap::new();
     let mut new_locations = Vec::new();
     let mut output_value = 0;
     for (vout, tx_out) in tx.output.iter().enumerate() {
       let end = output_value + tx_out.value;
 
+      while let Some((_, sat)) = rare_sats.next_if(|(offset, _)| *offset < end) {
+        if let Some(digests) = &mut self.digests {
+          digests
+            .entry(tx_out.script_pubkey.clone())
+            .or_default()
+            .rare_sats_gained
+            .push(sat);
+        }
+      }
+
       while let Some(flotsam) = inscriptions.peek() {
         if flotsam.offset >= end {
           break;
@@ -345,7 +450,13 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      self.update_inscription_location(
+        input_sat_ranges,
+        flotsam,
+        new_satpoint,
+        Some(&tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey),
+        bare_multisig,
+      )?;
     }
 
     if is_coinbase {
@@ -354,7 +465,13 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(
+          input_sat_ranges,
+          flotsam,
+          new_satpoint,
+          None,
+          bare_multisig,
+        )?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -368,6 +485,80 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     }
   }
 
+  pub(super) fn send_digests(&mut self) -> Result {
+    if let Some((sender, digests)) = self.event_sender.zip(self.digests.as_mut()) {
+      for (script_pubkey, mut digest) in mem::take(digests) {
+        Self::cancel_out(&mut digest.gained, &mut digest.lost);
+        Self::cancel_out(&mut digest.rare_sats_gained, &mut digest.rare_sats_lost);
+
+        if digest.gained.is_empty()
+          && digest.lost.is_empty()
+          && digest.rare_sats_gained.is_empty()
+          && digest.rare_sats_lost.is_empty()
+        {
+          continue;
+        }
+
+        sender.blocking_send(Event::InscriptionDigest {
+          block_height: self.height,
+          gained: digest.gained,
+          lost: digest.lost,
+          rare_sats_gained: digest.rare_sats_gained,
+          rare_sats_lost: digest.rare_sats_lost,
+          script_pubkey,
+        })?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn cancel_out<T: Copy + Ord>(gained: &mut Vec<T>, lost: &mut Vec<T>) {
+    let both = gained
+      .iter()
+      .copied()
+      .collect::<BTreeSet<T>>()
+      .intersection(&lost.iter().copied().collect())
+      .copied()
+      .collect::<BTreeSet<T>>();
+
+    gained.retain(|item| !both.contains(item));
+    lost.retain(|item| !both.contains(item));
+  }
+
+  fn is_bare_multisig(script_pubkey: &Script) -> bool {
+    let bytes = script_pubkey.as_bytes();
+
+    bytes.first().is_some_and(|first| {
+      (opcodes::all::OP_PUSHNUM_1.to_u8()..=opcodes::all::OP_PUSHNUM_16.to_u8()).contains(first)
+    }) && bytes.last() == Some(&opcodes::all::OP_CHECKMULTISIG.to_u8())
+  }
+
+  fn is_sanctioned(&self, script_pubkey: Option<&Script>) -> bool {
+    script_pubkey
+      .is_some_and(|script_pubkey| self.settings.sanctioned_scripts().contains(script_pubkey))
+  }
+
+  fn input_script_pubkey(&self, outpoint: OutPoint) -> Result<Option<ScriptBuf>> {
+    if let Some(tx) = self.block_transactions.get(&outpoint.txid) {
+      return Ok(
+        tx.output
+          .get(usize::try_from(outpoint.vout).unwrap())
+          .map(|tx_out| tx_out.script_pubkey.clone()),
+      );
+    }
+
+    let Some(outpoint_to_script_pubkey) = self.outpoint_to_script_pubkey else {
+      return Ok(None);
+    };
+
+    Ok(
+      outpoint_to_script_pubkey
+        .get(&outpoint.store())?
+        .map(|script_pubkey| ScriptBuf::from_bytes(script_pubkey.value().to_vec())),
+    )
+  }
+
   fn calculate_sat(
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     input_offset: u64,
@@ -392,10 +583,15 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    new_script_pubkey: Option<&Script>,
+    bare_multisig: bool,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
     let (unbound, sequence_number) = match flotsam.origin {
-      Origin::Old { old_satpoint } => {
+      Origin::Old {
+        old_satpoint,
+        old_script_pubkey,
+      } => {
         self
           .satpoint_to_sequence_number
           .remove_all(&old_satpoint.store())?;
@@ -407,24 +603,60 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           .value();
 
         if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
-            block_height: self.height,
-            inscription_id,
-            new_location: new_satpoint,
-            old_location: old_satpoint,
-            sequence_number,
-          })?;
+          let self_transfer =
+            new_script_pubkey.is_some() && old_script_pubkey.as_deref() == new_script_pubkey;
+
+          let sanctioned_counterparty = self.is_sanctioned(old_script_pubkey.as_deref())
+            || self.is_sanctioned(new_script_pubkey);
+
+          if let Some(digests) = &mut self.digests {
+            if let Some(old_script_pubkey) = old_script_pubkey {
+              digests
+                .entry(old_script_pubkey)
+                .or_default()
+                .lost
+                .push(inscription_id);
+            }
+
+            if let Some(new_script_pubkey) = new_script_pubkey {
+              digests
+                .entry(new_script_pubkey.into())
+                .or_default()
+                .gained
+                .push(inscription_id);
+            }
+          }
+
+          if !(self_transfer && self.settings.suppress_self_transfers()) {
+            sender.blocking_send(Event::InscriptionTransferred {
+              bare_multisig,
+              block_height: self.height,
+              inscription_id,
+              new_location: new_satpoint,
+              old_location: old_satpoint,
+              sanctioned_counterparty,
+              self_transfer,
+              sequence_number,
+            })?;
+          }
         }
 
         (false, sequence_number)
       }
       Origin::New {
+        content_type_mismatch,
         cursed,
+        exceeds_limits,
         fee,
+        flagged,
         hidden,
         parents,
         pointer: _,
+        protocol,
         reinscription,
+        reinscription_of,
+        sanctioned_input,
+        traits,
         unbound,
         vindicated,
       } => {
@@ -499,13 +731,34 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           .collect::<Result<Vec<u32>>>()?;
 
         if let Some(sender) = self.event_sender {
+          if let Some((digests, new_script_pubkey)) = self
+            .digests
+            .as_mut()
+            .zip(new_script_pubkey.filter(|_| !unbound))
+          {
+            digests
+              .entry(new_script_pubkey.into())
+              .or_default()
+              .gained
+              .push(inscription_id);
+          }
+
           sender.blocking_send(Event::InscriptionCreated {
+            bare_multisig,
             block_height: self.height,
             charms,
+            content_type_mismatch,
+            exceeds_limits,
+            flagged,
             inscription_id,
             location: (!unbound).then_some(new_satpoint),
             parent_inscription_ids: parents,
+            protocol,
+            reinscription_of,
+            sanctioned_counterparty: sanctioned_input || self.is_sanctioned(new_script_pubkey),
+            sat,
             sequence_number,
+            traits,
           })?;
         }
 
@@ -563,6 +816,12 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
       .sequence_number_to_satpoint
       .insert(sequence_number, &satpoint)?;
 
+    if self.index_inscription_history {
+      self
+        .sequence_number_and_height_to_satpoint
+        .insert((sequence_number, self.height), &satpoint)?;
+    }
+
     Ok(())
   }
 }
@@ -4,7 +4,7 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) event_sender: Option<&'a EventSender>,
   pub(super) height: u32,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
@@ -129,7 +129,15 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     let mut burned: HashMap<RuneId, Lot> = HashMap::new();
 
-    if let Some(Artifact::Cenotaph(_)) = artifact {
+    if let Some(Artifact::Cenotaph(cenotaph)) = &artifact {
+      if let Some(sender) = self.event_sender {
+        sender.blocking_send(Event::RuneCenotaph {
+          block_height: self.height,
+          flaw: cenotaph.flaw,
+          txid,
+        })?;
+      }
+
       for (id, balance) in unallocated {
         *burned.entry(id).or_default() += balance;
       }
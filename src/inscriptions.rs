@@ -4,11 +4,14 @@ use tag::Tag;
 
 pub(crate) use self::{envelope::ParsedEnvelope, media::Media};
 
-pub use self::{envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId};
+pub use self::{
+  envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId, protocol::Protocol,
+};
 
 mod envelope;
 mod inscription;
 pub(crate) mod inscription_id;
 pub(crate) mod media;
+mod protocol;
 mod tag;
 pub(crate) mod teleburn;
@@ -226,6 +226,14 @@ impl Inscription {
     Some(self.body()?.len())
   }
 
+  pub(crate) fn content_type_mismatch(&self) -> bool {
+    self
+      .content_type()
+      .zip(self.body())
+      .map(|(content_type, body)| Media::content_type_mismatch(content_type, body))
+      .unwrap_or_default()
+  }
+
   pub(crate) fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
@@ -290,21 +298,70 @@ impl Inscription {
   }
 
   pub(crate) fn hidden(&self) -> bool {
-    use regex::bytes::Regex;
-
     const BVM_NETWORK: &[u8] = b"<body style=\"background:#F61;color:#fff;\">\
                         <h1 style=\"height:100%\">bvm.network</h1></body>";
 
-    lazy_static! {
-      static ref BRC_420: Regex = Regex::new(r"^\s*/content/[[:xdigit:]]{64}i\d+\s*$").unwrap();
+    self.metaprotocol.is_some()
+      || matches!(self.media(), Media::Code(_) | Media::Text | Media::Unknown)
+      || self
+        .body()
+        .map(|body| body.starts_with(BVM_NETWORK))
+        .unwrap_or_default()
+      || self.protocol() == Some(Protocol::Brc420)
+  }
+
+  pub(crate) fn protocol(&self) -> Option<Protocol> {
+    Protocol::detect(self)
+  }
+
+  pub(crate) fn traits(&self) -> BTreeMap<String, String> {
+    fn text(value: &Value) -> Option<String> {
+      let text = match value {
+        Value::Text(text) => text.trim().into(),
+        Value::Integer(integer) => i128::from(*integer).to_string(),
+        Value::Float(float) => float.to_string(),
+        Value::Bool(bool) => bool.to_string(),
+        _ => return None,
+      };
+
+      (!text.is_empty()).then_some(text)
     }
 
-    self
-      .body()
-      .map(|body| BRC_420.is_match(body) || body.starts_with(BVM_NETWORK))
-      .unwrap_or_default()
-      || self.metaprotocol.is_some()
-      || matches!(self.media(), Media::Code(_) | Media::Text | Media::Unknown)
+    fn trait_key(value: &Value) -> Option<String> {
+      text(value).map(|key| key.to_lowercase())
+    }
+
+    fn field<'a>(map: &'a [(Value, Value)], name: &str) -> Option<&'a Value> {
+      map
+        .iter()
+        .find(|(key, _)| trait_key(key).as_deref() == Some(name))
+        .map(|(_, value)| value)
+    }
+
+    let Some(Value::Map(metadata)) = self.metadata() else {
+      return BTreeMap::new();
+    };
+
+    match field(&metadata, "traits").or_else(|| field(&metadata, "attributes")) {
+      Some(Value::Map(traits)) => traits
+        .iter()
+        .filter_map(|(key, value)| Some((trait_key(key)?, text(value)?)))
+        .collect(),
+      Some(Value::Array(traits)) => traits
+        .iter()
+        .filter_map(|entry| {
+          let Value::Map(entry) = entry else {
+            return None;
+          };
+
+          Some((
+            trait_key(field(entry, "trait_type")?)?,
+            text(field(entry, "value")?)?,
+          ))
+        })
+        .collect(),
+      _ => BTreeMap::new(),
+    }
   }
 }
 
@@ -907,4 +964,73 @@ mod tests {
     }
     .hidden());
   }
+
+  #[test]
+  fn traits() {
+    #[track_caller]
+    fn case(metadata: Value, expected: &[(&str, &str)]) {
+      let mut cbor = Vec::new();
+      ciborium::into_writer(&metadata, &mut cbor).unwrap();
+
+      assert_eq!(
+        Inscription {
+          metadata: Some(cbor),
+          ..default()
+        }
+        .traits(),
+        expected
+          .iter()
+          .map(|(key, value)| (key.to_string(), value.to_string()))
+          .collect::<BTreeMap<String, String>>(),
+      );
+    }
+
+    let text = |s: &str| Value::Text(s.into());
+
+    case(
+      Value::Map(vec![(
+        text("traits"),
+        Value::Map(vec![
+          (text(" Background "), text(" Blue ")),
+          (text("Level"), Value::Integer(7.into())),
+          (text("Legendary"), Value::Bool(true)),
+          (text("Empty"), text("")),
+          (text("Nested"), Value::Array(Vec::new())),
+        ]),
+      )]),
+      &[
+        ("background", "Blue"),
+        ("legendary", "true"),
+        ("level", "7"),
+      ],
+    );
+
+    case(
+      Value::Map(vec![(
+        text("Attributes"),
+        Value::Array(vec![
+          Value::Map(vec![
+            (text("trait_type"), text("Eyes")),
+            (text("value"), text("Laser")),
+          ]),
+          Value::Map(vec![(text("value"), text("orphan"))]),
+          text("foo"),
+        ]),
+      )]),
+      &[("eyes", "Laser")],
+    );
+
+    case(
+      Value::Map(vec![
+        (text("traits"), Value::Map(vec![(text("a"), text("b"))])),
+        (text("attributes"), Value::Map(vec![(text("c"), text("d"))])),
+      ]),
+      &[("a", "b")],
+    );
+
+    case(Value::Map(vec![(text("name"), text("foo"))]), &[]);
+    case(text("foo"), &[]);
+
+    assert!(Inscription::default().traits().is_empty());
+  }
 }
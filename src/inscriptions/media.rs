@@ -109,6 +109,58 @@ impl Media {
     ("video/webm",                  GENERIC, Video,            &["webm"]),
   ];
 
+  #[rustfmt::skip]
+  const SIGNATURES: &'static [(usize, &'static [u8], &'static [&'static str])] = &[
+    (0, b"\x89PNG\r\n\x1a\n",  &["image/png", "image/apng"]),
+    (0, b"\xff\xd8\xff",         &["image/jpeg"]),
+    (0, b"GIF87a",               &["image/gif"]),
+    (0, b"GIF89a",               &["image/gif"]),
+    (0, b"\x00\x00\x00\x0cJXL ", &["image/jxl"]),
+    (0, b"\xff\x0a",             &["image/jxl"]),
+    (0, b"%PDF-",                &["application/pdf"]),
+    (0, b"fLaC",                 &["audio/flac"]),
+    (0, b"ID3",                  &["audio/mpeg"]),
+    (0, b"\x1a\x45\xdf\xa3",     &["video/webm"]),
+    (0, b"wOFF",                 &["font/woff"]),
+    (0, b"wOF2",                 &["font/woff2"]),
+    (0, b"OTTO",                 &["font/otf"]),
+    (0, b"\x00\x01\x00\x00",     &["font/ttf", "font/otf"]),
+    (0, b"glTF",                 &["model/gltf-binary"]),
+    (4, b"ftypavif",             &["image/avif"]),
+    (4, b"ftyp",                 &["video/mp4"]),
+    (8, b"WEBP",                 &["image/webp"]),
+    (8, b"WAVE",                 &["audio/wav"]),
+  ];
+
+  /// Returns the content types consistent with the magic number at the start
+  /// of `body`, or `None` if `body` has no recognized signature.
+  pub(crate) fn sniff(body: &[u8]) -> Option<&'static [&'static str]> {
+    Self::SIGNATURES
+      .iter()
+      .find(|(offset, signature, _)| {
+        body
+          .get(*offset..)
+          .map(|body| body.starts_with(signature))
+          .unwrap_or_default()
+      })
+      .map(|(_, _, content_types)| *content_types)
+  }
+
+  pub(crate) fn content_type_mismatch(content_type: &str, body: &[u8]) -> bool {
+    let Some(content_types) = Self::sniff(body) else {
+      return false;
+    };
+
+    let essence = content_type
+      .split(';')
+      .next()
+      .unwrap_or_default()
+      .trim()
+      .to_lowercase();
+
+    !content_types.contains(&essence.as_str())
+  }
+
   pub(crate) fn content_type_for_path(
     path: &Path,
   ) -> Result<(&'static str, BrotliEncoderMode), Error> {
@@ -210,6 +262,30 @@ mod tests {
     );
   }
 
+  #[test]
+  fn content_type_mismatch() {
+    let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+
+    assert!(!Media::content_type_mismatch("image/png", png));
+    assert!(!Media::content_type_mismatch("image/apng", png));
+    assert!(!Media::content_type_mismatch("IMAGE/PNG; foo=bar", png));
+    assert!(Media::content_type_mismatch("image/jpeg", png));
+    assert!(Media::content_type_mismatch("text/html", png));
+
+    assert!(!Media::content_type_mismatch(
+      "image/webp",
+      b"RIFF\x00\x00\x00\x00WEBPVP8 "
+    ));
+    assert!(Media::content_type_mismatch(
+      "audio/wav",
+      b"RIFF\x00\x00\x00\x00WEBPVP8 "
+    ));
+
+    assert!(!Media::content_type_mismatch("text/plain", b"hello"));
+    assert!(!Media::content_type_mismatch("image/png", b"hello"));
+    assert!(!Media::content_type_mismatch("image/png", b""));
+  }
+
   #[test]
   fn h264_in_mp4_is_allowed() {
     assert!(Media::check_mp4_codec(Path::new("examples/h264.mp4")).is_ok(),);
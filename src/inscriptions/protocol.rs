@@ -0,0 +1,256 @@
+use {super::*, media::Language, regex::bytes::Regex};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Protocol {
+  Brc20,
+  Brc20SelfMint,
+  Brc420,
+  Orc20,
+  Tap,
+}
+
+impl Protocol {
+  pub(crate) const ALL: [Self; 5] = [
+    Self::Brc20,
+    Self::Brc20SelfMint,
+    Self::Brc420,
+    Self::Orc20,
+    Self::Tap,
+  ];
+
+  pub(crate) fn detect(inscription: &Inscription) -> Option<Self> {
+    lazy_static! {
+      static ref BRC_420: Regex = Regex::new(r"^\s*/content/[[:xdigit:]]{64}i\d+\s*$").unwrap();
+    }
+
+    #[derive(Deserialize)]
+    struct Payload {
+      op: Option<String>,
+      p: String,
+      self_mint: Option<serde_json::Value>,
+      tick: Option<String>,
+    }
+
+    let body = inscription.body()?;
+
+    if BRC_420.is_match(body) {
+      return Some(Self::Brc420);
+    }
+
+    if !matches!(
+      inscription.media(),
+      Media::Text | Media::Code(Language::Json)
+    ) {
+      return None;
+    }
+
+    let payload = serde_json::from_slice::<Payload>(body).ok()?;
+
+    match payload.p.as_str() {
+      "brc-20" => {
+        let tick = Self::normalize_brc20_tick(payload.tick.as_deref()?)?;
+
+        if payload.op.as_deref() == Some("deploy")
+          && tick.len() == 5
+          && payload.self_mint == Some("true".into())
+        {
+          Some(Self::Brc20SelfMint)
+        } else {
+          Some(Self::Brc20)
+        }
+      }
+      "orc-20" => Some(Self::Orc20),
+      "tap" => Some(Self::Tap),
+      _ => None,
+    }
+  }
+
+  /// Normalizes a BRC-20 tick. Ticks are compared case-insensitively and
+  /// must be four or five bytes long when UTF-8 encoded, so a single emoji
+  /// can make up an entire tick. The length is checked after lowercasing,
+  /// since lowercasing can change the length of non-ASCII characters. Returns
+  /// `None` if `tick` is not a valid tick.
+  pub fn normalize_brc20_tick(tick: &str) -> Option<String> {
+    let tick = tick.to_lowercase();
+    matches!(tick.len(), 4 | 5).then_some(tick)
+  }
+
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::Brc20 => "brc-20",
+      Self::Brc20SelfMint => "brc-20-self-mint",
+      Self::Brc420 => "brc-420",
+      Self::Orc20 => "orc-20",
+      Self::Tap => "tap",
+    }
+  }
+}
+
+impl Display for Protocol {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(content_type: &str, body: &str, expected: Option<Protocol>) {
+    assert_eq!(
+      Protocol::detect(&Inscription {
+        content_type: Some(content_type.as_bytes().into()),
+        body: Some(body.as_bytes().into()),
+        ..default()
+      }),
+      expected,
+    );
+  }
+
+  #[test]
+  fn detect() {
+    case(
+      "text/plain;charset=utf-8",
+      r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "application/json",
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"21000000"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"orc-20","op":"mint","tick":"orc","id":"1","amt":"1"}"#,
+      Some(Protocol::Orc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"tap","op":"token-transfer","tick":"tap","amt":"1"}"#,
+      Some(Protocol::Tap),
+    );
+    case(
+      "text/html",
+      "/content/09a8d837ec0bcaec668ecf405e696a16bee5990863659c224ff888fb6f8f45e7i0",
+      Some(Protocol::Brc420),
+    );
+  }
+
+  #[test]
+  fn detect_requires_known_protocol() {
+    case(
+      "text/plain",
+      r#"{"p":"sns","op":"reg","name":"foo.sats"}"#,
+      None,
+    );
+    case("text/plain", r#"{"op":"mint","tick":"ordi"}"#, None);
+    case("text/plain", r#"{"p":1}"#, None);
+    case("text/plain", "brc-20", None);
+  }
+
+  #[test]
+  fn detect_requires_text_or_json_content_type() {
+    case(
+      "text/html",
+      r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+      None,
+    );
+    case(
+      "image/png",
+      r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+      None,
+    );
+  }
+
+  #[test]
+  fn detect_requires_valid_brc20_tick() {
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","amt":"1"}"#,
+      None,
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","tick":"abc","amt":"1"}"#,
+      None,
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","tick":"abcdef","amt":"1"}"#,
+      None,
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","tick":"ORDI","amt":"1"}"#,
+      Some(Protocol::Brc20),
+    );
+  }
+
+  #[test]
+  fn detect_self_mint() {
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"deploy","tick":"pizza","max":"21000000","self_mint":"true"}"#,
+      Some(Protocol::Brc20SelfMint),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"deploy","tick":"PIZZA","max":"21000000","self_mint":"true"}"#,
+      Some(Protocol::Brc20SelfMint),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","tick":"pizza","amt":"1","self_mint":"true"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"mint","tick":"PIZZA","amt":"1"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"deploy","tick":"pizza","max":"21000000"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1","self_mint":"true"}"#,
+      Some(Protocol::Brc20),
+    );
+    case(
+      "text/plain",
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1","self_mint":"false"}"#,
+      Some(Protocol::Brc20),
+    );
+  }
+
+  #[test]
+  fn normalize_brc20_tick() {
+    assert_eq!(Protocol::normalize_brc20_tick("ordi").unwrap(), "ordi");
+    assert_eq!(Protocol::normalize_brc20_tick("OrDi").unwrap(), "ordi");
+    assert_eq!(Protocol::normalize_brc20_tick("pizza").unwrap(), "pizza");
+    assert_eq!(Protocol::normalize_brc20_tick("😀").unwrap(), "😀");
+    assert_eq!(Protocol::normalize_brc20_tick("a😀").unwrap(), "a😀");
+    assert_eq!(Protocol::normalize_brc20_tick("ab😀"), None);
+    assert_eq!(Protocol::normalize_brc20_tick("ord"), None);
+    assert_eq!(Protocol::normalize_brc20_tick(""), None);
+  }
+
+  #[test]
+  fn normalize_non_ascii_brc20_tick() {
+    assert_eq!(Protocol::normalize_brc20_tick("ÀBC").unwrap(), "àbc");
+    assert_eq!(Protocol::normalize_brc20_tick("ABCẞ").unwrap(), "abcß");
+    assert_eq!(Protocol::normalize_brc20_tick("İİ"), None);
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(Protocol::Brc20.to_string(), "brc-20");
+    assert_eq!(Protocol::Brc20SelfMint.to_string(), "brc-20-self-mint");
+    assert_eq!(Protocol::Brc420.to_string(), "brc-420");
+    assert_eq!(Protocol::Orc20.to_string(), "orc-20");
+    assert_eq!(Protocol::Tap.to_string(), "tap");
+  }
+}
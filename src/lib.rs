@@ -25,7 +25,7 @@ use {
     },
     into_usize::IntoUsize,
     representation::Representation,
-    settings::Settings,
+    settings::{Settings, Suppression},
     subcommand::{Subcommand, SubcommandResult},
     tally::Tally,
   },
@@ -51,8 +51,8 @@ use {
   http::HeaderMap,
   lazy_static::lazy_static,
   ordinals::{
-    varint, Artifact, Charm, Edict, Epoch, Etching, Height, Pile, Rarity, Rune, RuneId, Runestone,
-    Sat, SatPoint, SpacedRune, Terms,
+    varint, Artifact, Charm, Edict, Epoch, Etching, Flaw, Height, Pile, Rarity, Rune, RuneId,
+    Runestone, Sat, SatPoint, SpacedRune, Terms,
   },
   regex::Regex,
   reqwest::Url,
@@ -84,8 +84,8 @@ use {
 pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
-  index::{Index, RuneEntry},
-  inscriptions::{Envelope, Inscription, InscriptionId},
+  index::{EventFilter, Index, RuneEntry},
+  inscriptions::{Envelope, Inscription, InscriptionId, Protocol},
   object::Object,
   options::Options,
   wallet::transaction_builder::{Target, TransactionBuilder},
@@ -115,6 +115,7 @@ pub mod outgoing;
 mod re;
 mod representation;
 pub mod runes;
+mod sat_pattern;
 mod settings;
 pub mod subcommand;
 mod tally;
@@ -36,6 +36,11 @@ pub struct Options {
   pub(crate) cookie_file: Option<PathBuf>,
   #[arg(long, alias = "datadir", help = "Store index in <DATA_DIR>.")]
   pub(crate) data_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Only send events matching <EVENT_FILTER>, for example `event_type == inscription_created && protocol != brc-20`."
+  )]
+  pub(crate) event_filter: Option<EventFilter>,
   #[arg(
     long,
     help = "Don't look for inscriptions below <FIRST_INSCRIPTION_HEIGHT>."
@@ -45,11 +50,15 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(long, help = "Track unspent outputs by address.")]
+  pub(crate) index_addresses: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Track the location history of inscriptions.")]
+  pub(crate) index_inscription_history: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -61,6 +70,8 @@ pub struct Options {
   pub(crate) index_spent_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(long, help = "Identify this instance as <INSTANCE_ID> in `/status`.")]
+  pub(crate) instance_id: Option<String>,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
   #[arg(long, help = "Minify JSON output.")]
@@ -88,4 +99,10 @@ pub struct Options {
   pub(crate) signet: bool,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
   pub(crate) testnet: bool,
+  #[arg(
+    long,
+    value_delimiter = ',',
+    help = "Do not store the location of <UNTRACKED_RARITIES> sats in the sat index. Their location is found by scanning sat ranges instead."
+  )]
+  pub(crate) untracked_rarities: Vec<Rarity>,
 }
@@ -0,0 +1,362 @@
+use {
+  super::*,
+  std::{iter, ops::Range},
+};
+
+/// A pattern over the decimal digits of a sat number.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SatPattern {
+  Contains(Vec<u8>),
+  Palindrome,
+  Repeated,
+}
+
+impl SatPattern {
+  /// Sats in `range` matching this pattern, in ascending order.
+  pub(crate) fn matches(self, range: Range<u64>) -> impl Iterator<Item = Sat> {
+    let mut next = range.start;
+
+    iter::from_fn(move || {
+      let n = self.next(next).filter(|n| *n < range.end)?;
+      next = n + 1;
+      Some(Sat(n))
+    })
+  }
+
+  /// The smallest number not less than `n` matching this pattern.
+  fn next(&self, n: u64) -> Option<u64> {
+    match self {
+      Self::Contains(needle) => next_by_length(n, |lower| next_containing(lower, needle)),
+      Self::Palindrome => Palindromes::new(n.into()..u128::MAX)
+        .next()
+        .and_then(|n| n.try_into().ok()),
+      Self::Repeated => next_by_length(n, next_repeated),
+    }
+  }
+}
+
+/// The smallest number not less than `n` for which `next` finds a match
+/// among the numbers with the same number of digits, trying each number of
+/// digits in turn.
+fn next_by_length(n: u64, next: impl Fn(&[u8]) -> Option<Vec<u8>>) -> Option<u64> {
+  let n = digits(n);
+
+  (n.len()..=MAX_DIGITS)
+    .find_map(|len| {
+      if len == n.len() {
+        next(&n)
+      } else {
+        let mut lower = vec![0; len];
+        lower[0] = 1;
+        next(&lower)
+      }
+    })
+    .and_then(|digits| number(&digits))
+}
+
+impl FromStr for SatPattern {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "palindrome" => Ok(Self::Palindrome),
+      "repeated" => Ok(Self::Repeated),
+      _ => {
+        let needle = s
+          .strip_prefix("contains:")
+          .ok_or_else(|| anyhow!("unknown sat pattern `{s}`"))?;
+
+        ensure!(
+          !needle.is_empty()
+            && needle.len() <= MAX_DIGITS
+            && needle.chars().all(|c| c.is_ascii_digit()),
+          "sat pattern `{s}` must contain between 1 and {MAX_DIGITS} digits"
+        );
+
+        Ok(Self::Contains(needle.bytes().map(|b| b - b'0').collect()))
+      }
+    }
+  }
+}
+
+// enough digits for any u64
+const MAX_DIGITS: usize = 20;
+
+fn digits(n: u64) -> Vec<u8> {
+  n.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+fn number(digits: &[u8]) -> Option<u64> {
+  digits.iter().try_fold(0u64, |n, digit| {
+    n.checked_mul(10)?.checked_add((*digit).into())
+  })
+}
+
+/// Palindromic numbers in a range, in ascending order. Each palindrome is
+/// computed from its first half, the digits up to and including the middle
+/// one, without allocating. Successive palindromes of the same length have
+/// successive first halves.
+pub(crate) struct Palindromes {
+  end: u128,
+  half: u128,
+  len: u32,
+}
+
+impl Palindromes {
+  pub(crate) fn new(range: Range<u128>) -> Self {
+    let len = range.start.checked_ilog10().unwrap_or(0) + 1;
+
+    let mut palindromes = Self {
+      end: range.end,
+      half: range.start / 10u128.pow(len / 2),
+      len,
+    };
+
+    if palindromes
+      .palindrome()
+      .is_some_and(|palindrome| palindrome < range.start)
+    {
+      palindromes.advance();
+    }
+
+    palindromes
+  }
+
+  fn palindrome(&self) -> Option<u128> {
+    let mut palindrome = self.half;
+    let mut mirrored = self.half / 10u128.pow(self.len % 2);
+
+    for _ in 0..self.len / 2 {
+      palindrome = palindrome.checked_mul(10)?.checked_add(mirrored % 10)?;
+      mirrored /= 10;
+    }
+
+    Some(palindrome)
+  }
+
+  fn advance(&mut self) {
+    self.half += 1;
+
+    if self.half == 10u128.pow(self.len.div_ceil(2)) {
+      self.len += 1;
+      self.half = 10u128.pow(self.len.div_ceil(2) - 1);
+    }
+  }
+}
+
+impl Iterator for Palindromes {
+  type Item = u128;
+
+  fn next(&mut self) -> Option<u128> {
+    if self.len > u128::MAX.ilog10() + 1 {
+      return None;
+    }
+
+    let palindrome = self
+      .palindrome()
+      .filter(|palindrome| *palindrome < self.end)?;
+
+    self.advance();
+
+    Some(palindrome)
+  }
+}
+
+/// The smallest number of at least two identical digits with as many digits
+/// as `lower` and not less than it.
+fn next_repeated(lower: &[u8]) -> Option<Vec<u8>> {
+  if lower.len() < 2 {
+    return Some(vec![1, 1]);
+  }
+
+  (1..=9)
+    .map(|digit| vec![digit; lower.len()])
+    .find(|digits| digits.as_slice() >= lower)
+}
+
+/// The smallest number containing `needle` with as many digits as `lower` and
+/// not less than it. For each position of `needle`, the digits before it are
+/// either those of `lower`, or those of `lower` plus one followed by zeros.
+fn next_containing(lower: &[u8], needle: &[u8]) -> Option<Vec<u8>> {
+  if lower.len() < needle.len() {
+    return None;
+  }
+
+  (0..=lower.len() - needle.len())
+    .filter_map(|position| {
+      let end = position + needle.len();
+      let mut digits = lower[..position].to_vec();
+
+      match needle.cmp(&lower[position..end]) {
+        cmp::Ordering::Less => {
+          if position == 0 || digits.iter().all(|digit| *digit == 9) {
+            return None;
+          }
+          increment(&mut digits);
+          digits.extend(needle);
+          digits.resize(lower.len(), 0);
+        }
+        cmp::Ordering::Equal => digits.extend(&lower[position..]),
+        cmp::Ordering::Greater => {
+          digits.extend(needle);
+          digits.resize(lower.len(), 0);
+        }
+      }
+
+      (digits.len() == 1 || digits[0] != 0).then_some(digits)
+    })
+    .min()
+}
+
+fn increment(digits: &mut [u8]) {
+  for digit in digits.iter_mut().rev() {
+    if *digit == 9 {
+      *digit = 0;
+    } else {
+      *digit += 1;
+      return;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, proptest::prelude::*};
+
+  fn is_palindrome(n: u128) -> bool {
+    let s = n.to_string();
+    s.chars().rev().collect::<String>() == s
+  }
+
+  proptest! {
+    #[test]
+    fn palindromes_agree_with_brute_force(
+      start in prop_oneof![0..100_000_000u128, any::<u128>()],
+      len in 0..2000u128,
+    ) {
+      let range = start..start.saturating_add(len);
+
+      prop_assert_eq!(
+        Palindromes::new(range.clone()).collect::<Vec<u128>>(),
+        range.filter(|n| is_palindrome(*n)).collect::<Vec<u128>>()
+      );
+    }
+
+    #[test]
+    fn palindromes_around_palindromes_agree_with_brute_force(
+      n in any::<u128>(),
+      before in 0..1000u128,
+      after in 0..1000u128,
+    ) {
+      let palindrome = Palindromes::new(n..u128::MAX).next().unwrap_or(n);
+      let range = palindrome.saturating_sub(before)..palindrome.saturating_add(after);
+
+      prop_assert_eq!(
+        Palindromes::new(range.clone()).collect::<Vec<u128>>(),
+        range.filter(|n| is_palindrome(*n)).collect::<Vec<u128>>()
+      );
+    }
+  }
+
+  fn brute_force(pattern: &SatPattern, range: Range<u64>) -> Vec<u64> {
+    range
+      .filter(|n| {
+        let s = n.to_string();
+        match pattern {
+          SatPattern::Contains(needle) => s.contains(
+            &needle
+              .iter()
+              .map(|digit| char::from(b'0' + digit))
+              .collect::<String>(),
+          ),
+          SatPattern::Palindrome => s.chars().rev().collect::<String>() == s,
+          SatPattern::Repeated => s.len() > 1 && s.chars().all(|c| s.starts_with(c)),
+        }
+      })
+      .collect()
+  }
+
+  #[track_caller]
+  fn case(pattern: &str, range: Range<u64>) {
+    let pattern = pattern.parse::<SatPattern>().unwrap();
+    assert_eq!(
+      pattern
+        .clone()
+        .matches(range.clone())
+        .map(|sat| sat.n())
+        .collect::<Vec<u64>>(),
+      brute_force(&pattern, range),
+    );
+  }
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "palindrome".parse::<SatPattern>().unwrap(),
+      SatPattern::Palindrome
+    );
+    assert_eq!(
+      "repeated".parse::<SatPattern>().unwrap(),
+      SatPattern::Repeated
+    );
+    assert_eq!(
+      "contains:1009".parse::<SatPattern>().unwrap(),
+      SatPattern::Contains(vec![1, 0, 0, 9]),
+    );
+    assert!("foo".parse::<SatPattern>().is_err());
+    assert!("contains:".parse::<SatPattern>().is_err());
+    assert!("contains:12a".parse::<SatPattern>().is_err());
+    assert!("contains:000000000000000000000"
+      .parse::<SatPattern>()
+      .is_err());
+  }
+
+  #[test]
+  fn matches_agree_with_brute_force() {
+    for pattern in [
+      "palindrome",
+      "repeated",
+      "contains:0",
+      "contains:00",
+      "contains:1",
+      "contains:09",
+      "contains:90",
+      "contains:99",
+      "contains:123",
+      "contains:1001",
+    ] {
+      case(pattern, 0..200_000);
+      case(pattern, 5_000_000_000..5_000_200_000);
+      case(pattern, 999_990..1_000_010);
+    }
+  }
+
+  #[test]
+  fn sparse_matches_are_found_quickly() {
+    assert_eq!(
+      SatPattern::Palindrome
+        .matches(1_999_999_999_999_990..2_000_000_000_000_100)
+        .map(|sat| sat.n())
+        .collect::<Vec<u64>>(),
+      [1_999_999_999_999_991, 2_000_000_000_000_002],
+    );
+
+    assert_eq!(
+      "contains:2099999997689999"
+        .parse::<SatPattern>()
+        .unwrap()
+        .matches(0..Sat::SUPPLY)
+        .map(|sat| sat.n())
+        .collect::<Vec<u64>>(),
+      [2_099_999_997_689_999],
+    );
+
+    assert_eq!(
+      SatPattern::Repeated
+        .matches(5_000_000_000..10_000_000_000)
+        .map(|sat| sat.n())
+        .collect::<Vec<u64>>(),
+      [5555555555, 6666666666, 7777777777, 8888888888, 9999999999],
+    );
+  }
+}
@@ -1,4 +1,11 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {super::*, bitcoin::hashes::sha256, bitcoincore_rpc::Auth};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Suppression {
+  ContentHash(sha256::Hash),
+  ContentType(String),
+}
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default, deny_unknown_fields)]
@@ -13,20 +20,37 @@ pub struct Settings {
   config_dir: Option<PathBuf>,
   cookie_file: Option<PathBuf>,
   data_dir: Option<PathBuf>,
+  event_filter: Option<EventFilter>,
   first_inscription_height: Option<u32>,
+  flagged: Option<HashSet<InscriptionId>>,
+  flagged_content: Option<HashSet<sha256::Hash>>,
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
   index: Option<PathBuf>,
+  index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_inscription_history: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  inscription_digests: bool,
+  instance_id: Option<String>,
   integration_test: bool,
+  max_inscription_body_size: Option<usize>,
+  max_inscription_metadata_size: Option<usize>,
+  new_supply_events: bool,
   no_index_inscriptions: bool,
+  sanctioned_addresses: Option<PathBuf>,
+  #[serde(skip)]
+  sanctioned_scripts: HashSet<ScriptBuf>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  suppress_self_transfers: bool,
+  suppressed_content: Option<HashSet<sha256::Hash>>,
+  suppressed_content_types: Option<HashSet<String>>,
+  untracked_rarities: Option<HashSet<Rarity>>,
 }
 
 impl Settings {
@@ -117,9 +141,28 @@ impl Settings {
       config_dir: self.config_dir.or(source.config_dir),
       cookie_file: self.cookie_file.or(source.cookie_file),
       data_dir: self.data_dir.or(source.data_dir),
+      event_filter: self.event_filter.or(source.event_filter),
       first_inscription_height: self
         .first_inscription_height
         .or(source.first_inscription_height),
+      flagged: Some(
+        self
+          .flagged
+          .iter()
+          .flatten()
+          .chain(source.flagged.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
+      flagged_content: Some(
+        self
+          .flagged_content
+          .iter()
+          .flatten()
+          .chain(source.flagged_content.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
       height_limit: self.height_limit.or(source.height_limit),
       hidden: Some(
         self
@@ -131,16 +174,61 @@ impl Settings {
           .collect(),
       ),
       index: self.index.or(source.index),
+      index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_inscription_history: self.index_inscription_history || source.index_inscription_history,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
       index_spent_sats: self.index_spent_sats || source.index_spent_sats,
       index_transactions: self.index_transactions || source.index_transactions,
+      inscription_digests: self.inscription_digests || source.inscription_digests,
+      instance_id: self.instance_id.or(source.instance_id),
       integration_test: self.integration_test || source.integration_test,
+      max_inscription_body_size: self
+        .max_inscription_body_size
+        .or(source.max_inscription_body_size),
+      max_inscription_metadata_size: self
+        .max_inscription_metadata_size
+        .or(source.max_inscription_metadata_size),
+      new_supply_events: self.new_supply_events || source.new_supply_events,
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      sanctioned_addresses: self.sanctioned_addresses.or(source.sanctioned_addresses),
+      sanctioned_scripts: self
+        .sanctioned_scripts
+        .into_iter()
+        .chain(source.sanctioned_scripts)
+        .collect(),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      suppress_self_transfers: self.suppress_self_transfers || source.suppress_self_transfers,
+      suppressed_content: Some(
+        self
+          .suppressed_content
+          .iter()
+          .flatten()
+          .chain(source.suppressed_content.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
+      suppressed_content_types: Some(
+        self
+          .suppressed_content_types
+          .iter()
+          .flatten()
+          .chain(source.suppressed_content_types.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
+      untracked_rarities: Some(
+        self
+          .untracked_rarities
+          .iter()
+          .flatten()
+          .chain(source.untracked_rarities.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
     }
   }
 
@@ -161,20 +249,37 @@ impl Settings {
       config_dir: options.config_dir,
       cookie_file: options.cookie_file,
       data_dir: options.data_dir,
+      event_filter: options.event_filter,
       first_inscription_height: options.first_inscription_height,
+      flagged: None,
+      flagged_content: None,
       height_limit: options.height_limit,
       hidden: None,
       index: options.index,
+      index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_inscription_history: options.index_inscription_history,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
       index_spent_sats: options.index_spent_sats,
       index_transactions: options.index_transactions,
+      inscription_digests: false,
+      instance_id: options.instance_id,
       integration_test: options.integration_test,
+      max_inscription_body_size: None,
+      max_inscription_metadata_size: None,
+      new_supply_events: false,
       no_index_inscriptions: options.no_index_inscriptions,
+      sanctioned_addresses: None,
+      sanctioned_scripts: HashSet::new(),
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      suppress_self_transfers: false,
+      suppressed_content: None,
+      suppressed_content_types: None,
+      untracked_rarities: (!options.untracked_rarities.is_empty())
+        .then(|| options.untracked_rarities.into_iter().collect()),
     }
   }
 
@@ -213,6 +318,41 @@ impl Settings {
         })
     };
 
+    let hashes = |key| {
+      env
+        .get(key)
+        .map(|hashes| {
+          hashes
+            .split_whitespace()
+            .map(|hash| hash.parse::<sha256::Hash>())
+            .collect::<Result<HashSet<sha256::Hash>, bitcoin::hashes::hex::Error>>()
+        })
+        .transpose()
+        .with_context(|| format!("failed to parse environment variable ORD_{key} as hash list"))
+    };
+
+    let get_event_filter = |key| {
+      env
+        .get(key)
+        .map(|filter| filter.parse::<EventFilter>())
+        .transpose()
+        .with_context(|| format!("failed to parse environment variable ORD_{key} as event filter"))
+    };
+
+    let get_rarities = |key| {
+      env
+        .get(key)
+        .map(|rarities| {
+          rarities
+            .split_whitespace()
+            .map(|rarity| rarity.parse::<Rarity>())
+            .collect::<Result<HashSet<Rarity>, String>>()
+        })
+        .transpose()
+        .map_err(|err| anyhow!(err))
+        .with_context(|| format!("failed to parse environment variable ORD_{key} as rarity list"))
+    };
+
     let get_u32 = |key| {
       env
         .get(key)
@@ -239,20 +379,38 @@ impl Settings {
       config_dir: get_path("CONFIG_DIR"),
       cookie_file: get_path("COOKIE_FILE"),
       data_dir: get_path("DATA_DIR"),
+      event_filter: get_event_filter("EVENT_FILTER")?,
       first_inscription_height: get_u32("FIRST_INSCRIPTION_HEIGHT")?,
+      flagged: inscriptions("FLAGGED")?,
+      flagged_content: hashes("FLAGGED_CONTENT")?,
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
       index: get_path("INDEX"),
+      index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_inscription_history: get_bool("INDEX_INSCRIPTION_HISTORY"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
       index_spent_sats: get_bool("INDEX_SPENT_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
+      inscription_digests: get_bool("INSCRIPTION_DIGESTS"),
+      instance_id: get_string("INSTANCE_ID"),
       integration_test: get_bool("INTEGRATION_TEST"),
+      max_inscription_body_size: get_usize("MAX_INSCRIPTION_BODY_SIZE")?,
+      max_inscription_metadata_size: get_usize("MAX_INSCRIPTION_METADATA_SIZE")?,
+      new_supply_events: get_bool("NEW_SUPPLY_EVENTS"),
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      sanctioned_addresses: get_path("SANCTIONED_ADDRESSES"),
+      sanctioned_scripts: HashSet::new(),
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      suppress_self_transfers: get_bool("SUPPRESS_SELF_TRANSFERS"),
+      suppressed_content: hashes("SUPPRESSED_CONTENT")?,
+      suppressed_content_types: env
+        .get("SUPPRESSED_CONTENT_TYPES")
+        .map(|content_types| content_types.split_whitespace().map(Into::into).collect()),
+      untracked_rarities: get_rarities("UNTRACKED_RARITIES")?,
     })
   }
 
@@ -268,20 +426,36 @@ impl Settings {
       config_dir: None,
       cookie_file: None,
       data_dir: Some(dir.into()),
+      event_filter: None,
       first_inscription_height: None,
+      flagged: None,
+      flagged_content: None,
       height_limit: None,
       hidden: None,
       index: None,
+      index_addresses: false,
       index_cache_size: None,
+      index_inscription_history: false,
       index_runes: true,
       index_sats: true,
       index_spent_sats: false,
       index_transactions: false,
+      inscription_digests: false,
+      instance_id: None,
       integration_test: false,
+      max_inscription_body_size: None,
+      max_inscription_metadata_size: None,
+      new_supply_events: false,
       no_index_inscriptions: false,
+      sanctioned_addresses: None,
+      sanctioned_scripts: HashSet::new(),
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      suppress_self_transfers: false,
+      suppressed_content: None,
+      suppressed_content_types: None,
+      untracked_rarities: None,
     }
   }
 
@@ -318,6 +492,11 @@ impl Settings {
       None => data_dir.join("index.redb"),
     };
 
+    let sanctioned_scripts = match &self.sanctioned_addresses {
+      Some(path) => Self::load_sanctioned_scripts(path, chain)?,
+      None => HashSet::new(),
+    };
+
     Ok(Self {
       bitcoin_data_dir: Some(bitcoin_data_dir),
       bitcoin_rpc_password: self.bitcoin_rpc_password,
@@ -334,6 +513,7 @@ impl Settings {
       config_dir: None,
       cookie_file: Some(cookie_file),
       data_dir: Some(data_dir),
+      event_filter: self.event_filter,
       first_inscription_height: Some(if self.integration_test {
         0
       } else {
@@ -341,9 +521,12 @@ impl Settings {
           .first_inscription_height
           .unwrap_or_else(|| chain.first_inscription_height())
       }),
+      flagged: self.flagged,
+      flagged_content: self.flagged_content,
       height_limit: self.height_limit,
       hidden: self.hidden,
       index: Some(index),
+      index_addresses: self.index_addresses,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -352,15 +535,27 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_inscription_history: self.index_inscription_history,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
       index_spent_sats: self.index_spent_sats,
       index_transactions: self.index_transactions,
+      inscription_digests: self.inscription_digests,
+      instance_id: self.instance_id,
       integration_test: self.integration_test,
+      max_inscription_body_size: self.max_inscription_body_size,
+      max_inscription_metadata_size: self.max_inscription_metadata_size,
+      new_supply_events: self.new_supply_events,
       no_index_inscriptions: self.no_index_inscriptions,
+      sanctioned_addresses: self.sanctioned_addresses,
+      sanctioned_scripts,
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      suppress_self_transfers: self.suppress_self_transfers,
+      suppressed_content: self.suppressed_content,
+      suppressed_content_types: self.suppressed_content_types,
+      untracked_rarities: self.untracked_rarities,
     })
   }
 
@@ -486,6 +681,10 @@ impl Settings {
     self.data_dir.as_ref().unwrap().into()
   }
 
+  pub(crate) fn event_filter(&self) -> Option<&EventFilter> {
+    self.event_filter.as_ref()
+  }
+
   pub(crate) fn first_inscription_height(&self) -> u32 {
     self.first_inscription_height.unwrap()
   }
@@ -514,10 +713,18 @@ impl Settings {
     self.index_runes
   }
 
+  pub(crate) fn index_addresses(&self) -> bool {
+    self.index_addresses
+  }
+
   pub(crate) fn index_cache_size(&self) -> usize {
     self.index_cache_size.unwrap()
   }
 
+  pub(crate) fn index_inscription_history(&self) -> bool {
+    self.index_inscription_history
+  }
+
   pub(crate) fn index_sats(&self) -> bool {
     self.index_sats
   }
@@ -530,10 +737,30 @@ impl Settings {
     self.index_transactions
   }
 
+  pub(crate) fn inscription_digests(&self) -> bool {
+    self.inscription_digests
+  }
+
+  pub(crate) fn instance_id(&self) -> Option<&str> {
+    self.instance_id.as_deref()
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.integration_test
   }
 
+  pub(crate) fn new_supply_events(&self) -> bool {
+    self.new_supply_events
+  }
+
+  pub(crate) fn suppress_self_transfers(&self) -> bool {
+    self.suppress_self_transfers
+  }
+
+  pub(crate) fn untracked_rarities(&self) -> impl Iterator<Item = Rarity> + '_ {
+    self.untracked_rarities.iter().flatten().copied()
+  }
+
   pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self
       .hidden
@@ -542,6 +769,88 @@ impl Settings {
       .unwrap_or_default()
   }
 
+  pub(crate) fn is_flagged(
+    &self,
+    inscription_id: InscriptionId,
+    inscription: &Inscription,
+  ) -> bool {
+    self
+      .flagged
+      .as_ref()
+      .map(|flagged| flagged.contains(&inscription_id))
+      .unwrap_or_default()
+      || self
+        .flagged_content
+        .as_ref()
+        .zip(inscription.body())
+        .map(|(flagged_content, body)| flagged_content.contains(&sha256::Hash::hash(body)))
+        .unwrap_or_default()
+  }
+
+  pub(crate) fn exceeds_limits(&self, inscription: &Inscription) -> bool {
+    self
+      .max_inscription_body_size
+      .zip(inscription.content_length())
+      .map(|(max, length)| length > max)
+      .unwrap_or_default()
+      || self
+        .max_inscription_metadata_size
+        .zip(inscription.metadata.as_ref())
+        .map(|(max, metadata)| metadata.len() > max)
+        .unwrap_or_default()
+  }
+
+  pub(crate) fn suppression(&self, inscription: &Inscription) -> Option<Suppression> {
+    if let Some(content_type) = inscription.content_type().filter(|content_type| {
+      self
+        .suppressed_content_types
+        .as_ref()
+        .is_some_and(|suppressed_content_types| suppressed_content_types.contains(*content_type))
+    }) {
+      return Some(Suppression::ContentType(content_type.into()));
+    }
+
+    let hash = sha256::Hash::hash(inscription.body()?);
+
+    self
+      .suppressed_content
+      .as_ref()
+      .is_some_and(|suppressed_content| suppressed_content.contains(&hash))
+      .then_some(Suppression::ContentHash(hash))
+  }
+
+  pub(crate) fn sanctioned_scripts(&self) -> &HashSet<ScriptBuf> {
+    &self.sanctioned_scripts
+  }
+
+  fn load_sanctioned_scripts(path: &Path, chain: Chain) -> Result<HashSet<ScriptBuf>> {
+    fs::read_to_string(path)
+      .with_context(|| {
+        format!(
+          "failed to read sanctioned addresses from `{}`",
+          path.display()
+        )
+      })?
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty() && !line.starts_with('#'))
+      .map(|address| {
+        Ok(
+          address
+            .parse::<Address<NetworkUnchecked>>()?
+            .require_network(chain.network())?
+            .script_pubkey(),
+        )
+      })
+      .collect::<Result<HashSet<ScriptBuf>>>()
+      .with_context(|| {
+        format!(
+          "failed to parse sanctioned addresses in `{}`",
+          path.display()
+        )
+      })
+  }
+
   pub(crate) fn bitcoin_rpc_url(&self, wallet_name: Option<String>) -> String {
     let base_url = self.bitcoin_rpc_url.as_ref().unwrap();
     match wallet_name {
@@ -969,6 +1278,119 @@ mod tests {
     );
   }
 
+  #[test]
+  fn flagged_inscriptions_and_content() {
+    let flagged = inscription_id(1);
+    let foo = Inscription {
+      body: Some("foo".into()),
+      ..default()
+    };
+    let bar = Inscription {
+      body: Some("bar".into()),
+      ..default()
+    };
+
+    let settings = Settings {
+      flagged: Some(vec![flagged].into_iter().collect()),
+      flagged_content: Some(vec![sha256::Hash::hash(b"foo")].into_iter().collect()),
+      ..default()
+    };
+
+    assert!(settings.is_flagged(flagged, &bar));
+    assert!(settings.is_flagged(inscription_id(2), &foo));
+    assert!(!settings.is_flagged(inscription_id(2), &bar));
+    assert!(!settings.is_flagged(inscription_id(2), &Inscription::default()));
+    assert!(!Settings::default().is_flagged(flagged, &foo));
+  }
+
+  #[test]
+  fn inscription_limits() {
+    let settings = Settings {
+      max_inscription_body_size: Some(3),
+      max_inscription_metadata_size: Some(2),
+      ..default()
+    };
+
+    assert!(!settings.exceeds_limits(&Inscription {
+      body: Some("foo".into()),
+      metadata: Some(vec![0; 2]),
+      ..default()
+    }));
+    assert!(settings.exceeds_limits(&Inscription {
+      body: Some("food".into()),
+      ..default()
+    }));
+    assert!(settings.exceeds_limits(&Inscription {
+      metadata: Some(vec![0; 3]),
+      ..default()
+    }));
+    assert!(!Settings::default().exceeds_limits(&Inscription {
+      body: Some("food".into()),
+      metadata: Some(vec![0; 3]),
+      ..default()
+    }));
+  }
+
+  #[test]
+  fn suppression() {
+    let settings = Settings {
+      suppressed_content: Some(vec![sha256::Hash::hash(b"foo")].into_iter().collect()),
+      suppressed_content_types: Some(vec!["text/foo".into()].into_iter().collect()),
+      ..default()
+    };
+
+    assert_eq!(
+      settings.suppression(&inscription("text/plain", "foo")),
+      Some(Suppression::ContentHash(sha256::Hash::hash(b"foo"))),
+    );
+    assert_eq!(
+      settings.suppression(&inscription("text/foo", "bar")),
+      Some(Suppression::ContentType("text/foo".into())),
+    );
+    assert_eq!(
+      settings.suppression(&inscription("text/plain", "bar")),
+      None
+    );
+    assert_eq!(settings.suppression(&Inscription::default()), None);
+    assert_eq!(
+      serde_json::to_string(&Suppression::ContentType("text/foo".into())).unwrap(),
+      r#"{"content_type":"text/foo"}"#,
+    );
+  }
+
+  #[test]
+  fn sanctioned_scripts() {
+    assert!(Settings::default()
+      .or_defaults()
+      .unwrap()
+      .sanctioned_scripts()
+      .is_empty());
+
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("sanctioned.txt");
+
+    fs::write(
+      &path,
+      "# comment\n\nbc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4\n",
+    )
+    .unwrap();
+
+    let settings = Settings {
+      chain: Some(Chain::Mainnet),
+      sanctioned_addresses: Some(path.clone()),
+      ..default()
+    };
+
+    assert_eq!(
+      settings.clone().or_defaults().unwrap().sanctioned_scripts(),
+      &vec![address().script_pubkey()].into_iter().collect(),
+    );
+
+    fs::write(&path, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx\n").unwrap();
+
+    assert!(settings.or_defaults().is_err());
+  }
+
   #[test]
   fn example_config_file_is_valid() {
     let _: Settings = serde_yaml::from_reader(fs::File::open("ord.yaml").unwrap()).unwrap();
@@ -987,20 +1409,35 @@ mod tests {
       ("CONFIG_DIR", "config dir"),
       ("COOKIE_FILE", "cookie file"),
       ("DATA_DIR", "/data/dir"),
+      ("EVENT_FILTER", "event_type == rune_minted"),
       ("FIRST_INSCRIPTION_HEIGHT", "2"),
+      ("FLAGGED", "9d02b6b9e7ef3d5e5d9e8bfe1b8b7f1b6cd64d4b7d7e1e4d7a5f3c8e3f2b1a0ci0"),
+      ("FLAGGED_CONTENT", "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"),
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
       ("INDEX", "index"),
+      ("INDEX_ADDRESSES", "1"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_INSCRIPTION_HISTORY", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_SPENT_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
+      ("INSCRIPTION_DIGESTS", "1"),
+      ("INSTANCE_ID", "ord-1"),
       ("INTEGRATION_TEST", "1"),
+      ("MAX_INSCRIPTION_BODY_SIZE", "5"),
+      ("MAX_INSCRIPTION_METADATA_SIZE", "6"),
+      ("NEW_SUPPLY_EVENTS", "1"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("SANCTIONED_ADDRESSES", "sanctioned addresses"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("SUPPRESS_SELF_TRANSFERS", "1"),
+      ("SUPPRESSED_CONTENT", "fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9"),
+      ("SUPPRESSED_CONTENT_TYPES", "image/bmp text/foo"),
+      ("UNTRACKED_RARITIES", "uncommon rare"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -1019,7 +1456,26 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        event_filter: Some("event_type == rune_minted".parse().unwrap()),
         first_inscription_height: Some(2),
+        flagged: Some(
+          vec![
+            "9d02b6b9e7ef3d5e5d9e8bfe1b8b7f1b6cd64d4b7d7e1e4d7a5f3c8e3f2b1a0ci0"
+              .parse()
+              .unwrap()
+          ]
+          .into_iter()
+          .collect()
+        ),
+        flagged_content: Some(
+          vec![
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+              .parse()
+              .unwrap()
+          ]
+          .into_iter()
+          .collect()
+        ),
         height_limit: Some(3),
         hidden: Some(
           vec![
@@ -1034,16 +1490,41 @@ mod tests {
           .collect()
         ),
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_inscription_history: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        inscription_digests: true,
+        instance_id: Some("ord-1".into()),
         integration_test: true,
+        max_inscription_body_size: Some(5),
+        max_inscription_metadata_size: Some(6),
+        new_supply_events: true,
         no_index_inscriptions: true,
+        sanctioned_addresses: Some("sanctioned addresses".into()),
+        sanctioned_scripts: HashSet::new(),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        suppress_self_transfers: true,
+        suppressed_content: Some(
+          vec![
+            "fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9"
+              .parse()
+              .unwrap()
+          ]
+          .into_iter()
+          .collect()
+        ),
+        suppressed_content_types: Some(
+          vec!["image/bmp".into(), "text/foo".into()]
+            .into_iter()
+            .collect()
+        ),
+        untracked_rarities: Some(vec![Rarity::Uncommon, Rarity::Rare].into_iter().collect()),
       }
     );
   }
@@ -1064,18 +1545,23 @@ mod tests {
           "--config-dir=config dir",
           "--cookie-file=cookie file",
           "--datadir=/data/dir",
+          "--event-filter=event_type == rune_minted",
           "--first-inscription-height=2",
           "--height-limit=3",
+          "--index-addresses",
           "--index-cache-size=4",
+          "--index-inscription-history",
           "--index-runes",
           "--index-sats",
           "--index-spent-sats",
           "--index-transactions",
           "--index=index",
+          "--instance-id=ord-1",
           "--integration-test",
           "--no-index-inscriptions",
           "--server-password=server password",
           "--server-username=server username",
+          "--untracked-rarities=uncommon,rare",
         ])
         .unwrap()
       ),
@@ -1090,20 +1576,36 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        event_filter: Some("event_type == rune_minted".parse().unwrap()),
         first_inscription_height: Some(2),
+        flagged: None,
+        flagged_content: None,
         height_limit: Some(3),
         hidden: None,
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_inscription_history: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        inscription_digests: false,
+        instance_id: Some("ord-1".into()),
         integration_test: true,
+        max_inscription_body_size: None,
+        max_inscription_metadata_size: None,
+        new_supply_events: false,
         no_index_inscriptions: true,
+        sanctioned_addresses: None,
+        sanctioned_scripts: HashSet::new(),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        suppress_self_transfers: false,
+        suppressed_content: None,
+        suppressed_content_types: None,
+        untracked_rarities: Some(vec![Rarity::Uncommon, Rarity::Rare].into_iter().collect()),
       }
     );
   }
@@ -1,6 +1,7 @@
 use super::*;
 
 pub mod balances;
+mod client;
 pub mod decode;
 pub mod env;
 pub mod epochs;
@@ -21,6 +22,8 @@ pub mod wallet;
 pub(crate) enum Subcommand {
   #[command(about = "List all rune balances")]
   Balances,
+  #[command(about = "Query a remote ord server")]
+  Client(client::ClientCommand),
   #[command(about = "Decode a transaction")]
   Decode(decode::Decode),
   #[command(about = "Start a regtest ord and bitcoind instance")]
@@ -57,6 +60,7 @@ impl Subcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
       Self::Balances => balances::run(settings),
+      Self::Client(client) => client.run(settings),
       Self::Decode(decode) => decode.run(settings),
       Self::Env(env) => env.run(),
       Self::Epochs => epochs::run(),
@@ -0,0 +1,87 @@
+use {super::*, base64::Engine};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ClientCommand {
+  #[arg(
+    long,
+    help = "Query ord running at <SERVER_URL>. [default: http://localhost:80]"
+  )]
+  server_url: Option<Url>,
+  #[command(subcommand)]
+  subcommand: Subcommand,
+}
+
+#[derive(Debug, Parser)]
+enum Subcommand {
+  #[command(about = "Display an inscription")]
+  Inscription { inscription_id: InscriptionId },
+  #[command(about = "Display outputs and their sat ranges")]
+  Outputs {
+    #[arg(required = true)]
+    outpoints: Vec<OutPoint>,
+  },
+  #[command(about = "Display a sat")]
+  Sat { sat: Sat },
+  #[command(about = "Display server status")]
+  Status,
+}
+
+impl ClientCommand {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let url = self
+      .server_url
+      .as_ref()
+      .map(Url::as_str)
+      .or(settings.server_url())
+      .unwrap_or("http://127.0.0.1:80")
+      .parse::<Url>()
+      .context("invalid server URL")?;
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(
+      reqwest::header::ACCEPT,
+      reqwest::header::HeaderValue::from_static("application/json"),
+    );
+
+    if let Some((username, password)) = settings.credentials() {
+      let credentials =
+        base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"));
+      headers.insert(
+        reqwest::header::AUTHORIZATION,
+        reqwest::header::HeaderValue::from_str(&format!("Basic {credentials}"))?,
+      );
+    }
+
+    let client = reqwest::blocking::ClientBuilder::new()
+      .default_headers(headers)
+      .build()?;
+
+    let get = |path: String| -> Result<reqwest::blocking::Response> {
+      let response = client.get(url.join(&path)?).send()?;
+
+      if !response.status().is_success() {
+        bail!("{path}: {}: {}", response.status(), response.text()?);
+      }
+
+      Ok(response)
+    };
+
+    Ok(Some(match self.subcommand {
+      Subcommand::Inscription { inscription_id } => {
+        Box::new(get(format!("/inscription/{inscription_id}"))?.json::<api::Inscription>()?)
+      }
+      Subcommand::Outputs { outpoints } => {
+        let response = client.post(url.join("/outputs")?).json(&outpoints).send()?;
+
+        if !response.status().is_success() {
+          bail!("/outputs: {}: {}", response.status(), response.text()?);
+        }
+
+        Box::new(response.json::<Vec<api::Output>>()?)
+      }
+      Subcommand::Sat { sat } => Box::new(get(format!("/sat/{}", sat.n()))?.json::<api::Sat>()?),
+      Subcommand::Status => Box::new(get("/status".into())?.json::<api::Status>()?),
+    }))
+  }
+}
@@ -1,15 +1,27 @@
 use super::*;
 
 mod export;
+mod export_snapshot;
+mod holders;
+mod import_snapshot;
 pub mod info;
+pub mod provenance;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
+  #[command(about = "Write a compressed snapshot of the index to a file")]
+  ExportSnapshot(export_snapshot::ExportSnapshot),
+  #[command(about = "Write inscription holders at a block height to a CSV file")]
+  Holders(holders::Holders),
+  #[command(about = "Create the index from a snapshot file")]
+  ImportSnapshot(import_snapshot::ImportSnapshot),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Print every location an inscription has had")]
+  Provenance(provenance::Provenance),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -18,7 +30,11 @@ impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
       Self::Export(export) => export.run(settings),
+      Self::ExportSnapshot(export_snapshot) => export_snapshot.run(settings),
+      Self::Holders(holders) => holders.run(settings),
+      Self::ImportSnapshot(import_snapshot) => import_snapshot.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Provenance(provenance) => provenance.run(settings),
       Self::Update => update::run(settings),
     }
   }
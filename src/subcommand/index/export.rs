@@ -2,6 +2,11 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Export {
+  #[arg(
+    long,
+    help = "Export inscriptions and their locations as of block <HEIGHT>. Requires index created with `--index-inscription-history`."
+  )]
+  height: Option<u32>,
   #[arg(long, help = "Include addresses in export")]
   include_addresses: bool,
   #[arg(long, help = "Write export to <TSV>")]
@@ -13,7 +18,8 @@ impl Export {
     let index = Index::open(&settings)?;
 
     index.update()?;
-    index.export(&self.tsv, self.include_addresses)?;
+
+    index.export(&self.tsv, self.include_addresses, self.height)?;
 
     Ok(None)
   }
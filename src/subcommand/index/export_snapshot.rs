@@ -0,0 +1,22 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct ExportSnapshot {
+  #[arg(help = "Write snapshot to <PATH>.")]
+  path: PathBuf,
+}
+
+impl ExportSnapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let file = fs::File::create(&self.path)
+      .with_context(|| format!("failed to create snapshot `{}`", self.path.display()))?;
+
+    match Index::export_snapshot(&settings, file) {
+      Ok(header) => Ok(Some(Box::new(header))),
+      Err(err) => {
+        fs::remove_file(&self.path).ok();
+        Err(err)
+      }
+    }
+  }
+}
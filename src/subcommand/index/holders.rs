@@ -0,0 +1,60 @@
+use {
+  super::*,
+  std::io::{BufWriter, Write},
+};
+
+#[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("collection")
+    .required(true)
+    .args(&["parent", "inscriptions"]),
+))]
+pub(crate) struct Holders {
+  #[arg(long, help = "Resolve holders of children of <PARENT>.")]
+  parent: Option<InscriptionId>,
+  #[arg(long, num_args = 1.., help = "Resolve holders of <INSCRIPTIONS>.")]
+  inscriptions: Option<Vec<InscriptionId>>,
+  #[arg(long, help = "Resolve holders as of block <HEIGHT>.")]
+  height: u32,
+  #[arg(long, help = "Write holders to <CSV>.")]
+  csv: PathBuf,
+}
+
+impl Holders {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    let inscriptions = match (self.parent, self.inscriptions) {
+      (Some(parent), _) => index.get_children_by_inscription_id(parent)?,
+      (None, Some(inscriptions)) => inscriptions,
+      (None, None) => unreachable!(),
+    };
+
+    let mut writer = BufWriter::new(fs::File::create(&self.csv)?);
+
+    writeln!(writer, "inscription_id,satpoint,address")?;
+
+    for inscription_id in inscriptions {
+      let Some(satpoint) = index.get_inscription_satpoint_at_height(inscription_id, self.height)?
+      else {
+        continue;
+      };
+
+      let address = index.get_address_for_satpoint(satpoint)?;
+
+      writeln!(
+        writer,
+        "{inscription_id},{satpoint},{}",
+        address
+          .map(|address| address.to_string())
+          .unwrap_or_default()
+      )?;
+    }
+
+    writer.flush()?;
+
+    Ok(None)
+  }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct ImportSnapshot {
+  #[arg(help = "Read snapshot from <PATH>.")]
+  path: PathBuf,
+}
+
+impl ImportSnapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let file = fs::File::open(&self.path)
+      .with_context(|| format!("failed to open snapshot `{}`", self.path.display()))?;
+
+    Ok(Some(Box::new(Index::import_snapshot(&settings, file)?)))
+  }
+}
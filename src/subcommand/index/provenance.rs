@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Provenance {
+  #[arg(help = "Print the location history of <INSCRIPTION_ID>.")]
+  inscription_id: InscriptionId,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub inscription_id: InscriptionId,
+  pub locations: Vec<api::InscriptionLocation>,
+}
+
+impl Provenance {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    let history = index
+      .get_inscription_location_history(self.inscription_id)?
+      .ok_or_else(|| anyhow!("inscription {} not found", self.inscription_id))?;
+
+    let mut locations = Vec::new();
+
+    for (height, satpoint) in history {
+      let address = index
+        .get_address_for_satpoint(satpoint)?
+        .map(|address| address.to_string());
+
+      locations.push(api::InscriptionLocation {
+        address,
+        height,
+        satpoint,
+      });
+    }
+
+    Ok(Some(Box::new(Output {
+      inscription_id: self.inscription_id,
+      locations,
+    })))
+  }
+}
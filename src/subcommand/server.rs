@@ -2,25 +2,34 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    accept_ndjson::AcceptNdjson,
     error::{OptionExt, ServerError, ServerResult},
+    listener::Listener,
+    scan_pool::ScanPool,
+    single_flight::SingleFlight,
   },
   super::*,
-  crate::templates::{
-    BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml, InputHtml,
-    InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml,
-    ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+  crate::{
+    sat_pattern::SatPattern,
+    templates::{
+      BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml, InputHtml,
+      InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml,
+      ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
+      PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
+      PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+    },
   },
   axum::{
     body,
-    extract::{Extension, Json, Path, Query},
+    error_handling::HandleErrorLayer,
+    extract::{DefaultBodyLimit, Extension, Json, Path, Query, State},
     http::{header, HeaderValue, StatusCode, Uri},
+    middleware::{self, Next},
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     Router,
   },
-  axum_server::Handle,
+  axum_server::{Handle, HttpConfig},
   brotli::Decompressor,
   rust_embed::RustEmbed,
   rustls_acme::{
@@ -30,10 +39,13 @@ use {
     AcmeConfig,
   },
   std::{cmp::Ordering, str, sync::Arc},
+  tokio::sync::{Notify, Semaphore},
   tokio_stream::StreamExt,
+  tower::{BoxError, ServiceBuilder},
   tower_http::{
-    compression::CompressionLayer,
-    cors::{Any, CorsLayer},
+    compression::{CompressionLayer, CompressionLevel},
+    cors::{AllowOrigin, Any, CorsLayer},
+    decompression::RequestDecompressionLayer,
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
   },
@@ -43,9 +55,18 @@ pub(crate) use server_config::ServerConfig;
 
 mod accept_encoding;
 mod accept_json;
+mod accept_ndjson;
 mod error;
+mod listener;
 pub mod query;
+mod scan_pool;
 mod server_config;
+mod single_flight;
+
+const LIMITED_BODY_SIZE: usize = 64 * 1024;
+const LIMITED_CONCURRENCY: usize = 64;
+const SNAPSHOT_HEIGHT: &str = "ord-snapshot-height";
+const SPEND_BURIAL_DEPTH: u32 = 6;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -53,11 +74,20 @@ enum SpawnConfig {
   Redirect(String),
 }
 
+type OutputLookups =
+  SingleFlight<(OutPoint, Option<u32>), Result<Option<(api::Output, TxOut)>, Arc<Error>>>;
+
 #[derive(Deserialize)]
 struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct SatRangesQuery {
+  #[serde(default)]
+  include_inscriptions: bool,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -91,6 +121,33 @@ pub struct Server {
     help = "Request ACME TLS certificate for <ACME_DOMAIN>. This ord instance must be reachable at <ACME_DOMAIN>:443 to respond to Let's Encrypt ACME challenges."
   )]
   pub(crate) acme_domain: Vec<String>,
+  #[arg(
+    long,
+    conflicts_with = "limit_requests",
+    help = "Accept gzip and brotli compressed request bodies. A decompressed request body may be arbitrarily large, making decompression a DoS vector."
+  )]
+  pub(crate) accept_compressed_requests: bool,
+  #[arg(
+    long,
+    conflicts_with = "disable_compression",
+    help = "Compress responses at <COMPRESSION_LEVEL>. Levels are algorithm-specific and clamped to the algorithm's maximum."
+  )]
+  pub(crate) compression_level: Option<u32>,
+  #[arg(
+    long,
+    help = "Allow cross-origin requests from <CORS_ORIGIN>. May be given multiple times. [default: any origin]"
+  )]
+  pub(crate) cors_origin: Vec<String>,
+  #[arg(
+    long,
+    help = "Allow <CORS_HEADER> in cross-origin requests. May be given multiple times."
+  )]
+  pub(crate) cors_header: Vec<String>,
+  #[arg(
+    long,
+    help = "Allow browsers to cache CORS preflight responses for <CORS_MAX_AGE>."
+  )]
+  pub(crate) cors_max_age: Option<humantime::Duration>,
   #[arg(
     long,
     help = "Use <CSP_ORIGIN> in Content-Security-Policy header. Set this to the public-facing URL of your ord instance."
@@ -101,6 +158,10 @@ pub struct Server {
     help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
   )]
   pub(crate) decompress: bool,
+  #[arg(long, help = "Do not compress responses.")]
+  pub(crate) disable_compression: bool,
+  #[arg(long, help = "Close HTTP/1 connections after each response.")]
+  pub(crate) disable_keep_alive: bool,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
   #[arg(
@@ -122,10 +183,68 @@ pub struct Server {
   pub(crate) http: bool,
   #[arg(long, help = "Serve HTTPS traffic on <HTTPS_PORT>.")]
   pub(crate) https: bool,
+  #[arg(long, help = "Only serve HTTP/1 and disable HTTP/2.")]
+  pub(crate) http1_only: bool,
+  #[arg(
+    long,
+    conflicts_with = "http1_only",
+    help = "Send HTTP/2 keep-alive pings every <HTTP2_KEEP_ALIVE_INTERVAL>."
+  )]
+  pub(crate) http2_keep_alive_interval: Option<humantime::Duration>,
+  #[arg(
+    long,
+    requires = "http2_keep_alive_interval",
+    help = "Close HTTP/2 connections if keep-alive pings are not acknowledged within <HTTP2_KEEP_ALIVE_TIMEOUT>. [default: 20s]"
+  )]
+  pub(crate) http2_keep_alive_timeout: Option<humantime::Duration>,
+  #[arg(
+    long,
+    conflicts_with = "http1_only",
+    help = "Allow at most <HTTP2_MAX_CONCURRENT_STREAMS> concurrent streams per HTTP/2 connection."
+  )]
+  pub(crate) http2_max_concurrent_streams: Option<u32>,
+  #[arg(
+    long,
+    help = "Also listen for incoming HTTP requests on <LISTENER>, of the form `PORT=PREFIX[,PREFIX...]`, serving only paths that start with one of the given prefixes. May be given multiple times."
+  )]
+  pub(crate) listener: Vec<Listener>,
+  #[arg(
+    long,
+    help = "Reject request bodies larger than <MAX_BODY_SIZE> bytes. [default: 2 MiB, or 64 KiB with --limit-requests]"
+  )]
+  pub(crate) max_body_size: Option<usize>,
   #[arg(long, help = "Redirect HTTP traffic to HTTPS.")]
   pub(crate) redirect_http_to_https: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    conflicts_with = "decompress",
+    help = "Limit request bodies to 64 KiB unless --max-body-size is given, and respond with 429 Too Many Requests when more than 64 requests are in flight."
+  )]
+  pub(crate) limit_requests: bool,
+  #[arg(
+    long,
+    help = "Also listen for incoming HTTP requests on the Unix domain socket at <UNIX_SOCKET>."
+  )]
+  pub(crate) unix_socket: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "8",
+    help = "Look up at most <OUTPUT_LOOKUP_CONCURRENCY> outputs concurrently when serving `POST /outputs`."
+  )]
+  pub(crate) output_lookup_concurrency: usize,
+  #[arg(
+    long,
+    default_value = "2",
+    help = "Run sat pattern, sat name, rare sat, and address scans on at most <SCAN_THREADS> threads at a time."
+  )]
+  pub(crate) scan_threads: usize,
+  #[arg(
+    long,
+    help = "Stop scans that take longer than <SCAN_TIMEOUT> and respond with 503 Service Unavailable."
+  )]
+  pub(crate) scan_timeout: Option<humantime::Duration>,
   #[arg(
     long,
     help = "Proxy `/content/INSCRIPTION_ID` requests to `<CONTENT_PROXY>/content/INSCRIPTION_ID` if the inscription is not present on current chain."
@@ -176,6 +295,7 @@ impl Server {
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        output_lookup_concurrency: self.output_lookup_concurrency.max(1),
       });
 
       let router = Router::new()
@@ -223,24 +343,32 @@ impl Server {
           get(Self::parents_paginated),
         )
         .route("/preview/:inscription_id", get(Self::preview))
+        .route("/r/address/:address/rarities", get(Self::address_rarities))
         .route("/r/blockhash", get(Self::block_hash_json))
         .route(
           "/r/blockhash/:height",
           get(Self::block_hash_from_height_json),
         )
         .route("/r/blockheight", get(Self::block_height))
+        .route("/r/blockheight/at/:timestamp", get(Self::block_at_time))
         .route("/r/blocktime", get(Self::block_time))
         .route("/r/blockinfo/:query", get(Self::block_info))
         .route(
           "/r/inscription/:inscription_id",
           get(Self::inscription_recursive),
         )
+        .route(
+          "/r/inscription/:inscription_id/at/:height",
+          get(Self::inscription_location_at_height),
+        )
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
           get(Self::children_recursive_paginated),
         )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
+        .route("/r/raresats/:rarity", get(Self::rare_sats))
+        .route("/r/raresats/:rarity/:page", get(Self::rare_sats_paginated))
         .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
         .route(
           "/r/sat/:sat_number/:page",
@@ -250,6 +378,20 @@ impl Server {
           "/r/sat/:sat_number/at/:index",
           get(Self::sat_inscription_at_index),
         )
+        .route("/r/sat/:sat_number/rarities", get(Self::sat_rarities))
+        .route("/r/satnames/:prefix", get(Self::sat_names))
+        .route("/r/satpattern/:pattern/:range", get(Self::sat_pattern))
+        .route(
+          "/r/satpattern/:pattern/:range/:page",
+          get(Self::sat_pattern_paginated),
+        )
+        .route("/r/satnames/:prefix/:page", get(Self::sat_names_paginated))
+        .route("/r/utxo/:outpoint/ranges", get(Self::utxo_ranges))
+        .route(
+          "/r/utxo/:outpoint/ranges/:page",
+          get(Self::utxo_ranges_paginated),
+        )
+        .route("/r/utxo/:outpoint/rarities", get(Self::utxo_rarities))
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
@@ -265,6 +407,11 @@ impl Server {
         .route("/update", get(Self::update))
         .fallback(Self::fallback)
         .layer(Extension(index))
+        .layer(Extension(Arc::new(ScanPool::new(
+          self.scan_threads.max(1),
+          self.scan_timeout.map(Into::into),
+        ))))
+        .layer(Extension(Arc::new(OutputLookups::new())))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -275,36 +422,77 @@ impl Server {
           header::STRICT_TRANSPORT_SECURITY,
           HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
         ))
+        .layer(self.cors()?)
+        .layer(self.compression())
         .layer(
-          CorsLayer::new()
-            .allow_methods([http::Method::GET])
-            .allow_origin(Any),
+          ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(|err: BoxError| async move {
+              (StatusCode::BAD_REQUEST, err.to_string())
+            }))
+            .layer(
+              RequestDecompressionLayer::new()
+                .gzip(self.accept_compressed_requests)
+                .br(self.accept_compressed_requests),
+            ),
         )
-        .layer(CompressionLayer::new())
         .with_state(server_config);
 
+      let router = match self.max_body_size {
+        Some(max_body_size) => router.layer(DefaultBodyLimit::max(max_body_size)),
+        None if self.limit_requests => router.layer(DefaultBodyLimit::max(LIMITED_BODY_SIZE)),
+        None => router,
+      };
+
+      let router = if self.limit_requests {
+        router.layer(middleware::from_fn_with_state(
+          Arc::new(Semaphore::new(LIMITED_CONCURRENCY)),
+          Self::limit_concurrency,
+        ))
+      } else {
+        router
+      };
+
       let router = if let Some((username, password)) = settings.credentials() {
         router.layer(ValidateRequestHeaderLayer::basic(username, password))
       } else {
         router
       };
 
+      let mut servers = Vec::new();
+
+      for listener in &self.listener {
+        servers.push(self.spawn(
+          &settings,
+          router.clone().layer(middleware::from_fn_with_state(
+            Arc::new(listener.clone()),
+            Self::restrict_routes,
+          )),
+          handle.clone(),
+          listener.port,
+          SpawnConfig::Http,
+        )?);
+      }
+
+      let unix_shutdown = Arc::new(Notify::new());
+
+      let unix_server = self
+        .unix_socket
+        .as_ref()
+        .map(|path| Self::spawn_unix(router.clone(), path, unix_shutdown.clone()))
+        .transpose()?;
+
       match (self.http_port(), self.https_port()) {
         (Some(http_port), None) => {
-          self
-            .spawn(&settings, router, handle, http_port, SpawnConfig::Http)?
-            .await??
+          servers.push(self.spawn(&settings, router, handle, http_port, SpawnConfig::Http)?);
         }
         (None, Some(https_port)) => {
-          self
-            .spawn(
-              &settings,
-              router,
-              handle,
-              https_port,
-              SpawnConfig::Https(self.acceptor(&settings)?),
-            )?
-            .await??
+          servers.push(self.spawn(
+            &settings,
+            router,
+            handle,
+            https_port,
+            SpawnConfig::Https(self.acceptor(&settings)?),
+          )?);
         }
         (Some(http_port), Some(https_port)) => {
           let http_spawn_config = if self.redirect_http_to_https {
@@ -317,27 +505,50 @@ impl Server {
             SpawnConfig::Http
           };
 
-          let (http_result, https_result) = tokio::join!(
-            self.spawn(
-              &settings,
-              router.clone(),
-              handle.clone(),
-              http_port,
-              http_spawn_config
-            )?,
-            self.spawn(
-              &settings,
-              router,
-              handle,
-              https_port,
-              SpawnConfig::Https(self.acceptor(&settings)?),
-            )?
-          );
-          http_result.and(https_result)??;
+          servers.push(self.spawn(
+            &settings,
+            router.clone(),
+            handle.clone(),
+            http_port,
+            http_spawn_config,
+          )?);
+
+          servers.push(self.spawn(
+            &settings,
+            router,
+            handle,
+            https_port,
+            SpawnConfig::Https(self.acceptor(&settings)?),
+          )?);
         }
         (None, None) => unreachable!(),
       }
 
+      let servers = async {
+        // fail as soon as any listener does, e.g. because its port is taken
+        let result = futures::future::try_join_all(
+          servers
+            .into_iter()
+            .map(|server| async { anyhow::Ok(server.await??) }),
+        )
+        .await;
+
+        // the handle only stops the TCP servers, so once they have stopped,
+        // pass the shutdown on to the unix socket server
+        unix_shutdown.notify_one();
+
+        result
+      };
+
+      let unix_server = async {
+        match unix_server {
+          Some(unix_server) => unix_server.await?,
+          None => Ok(()),
+        }
+      };
+
+      tokio::try_join!(servers, unix_server)?;
+
       Ok(None)
     })
   }
@@ -376,11 +587,14 @@ impl Server {
       );
     }
 
+    let http_config = self.http_config();
+
     Ok(tokio::spawn(async move {
       match config {
         SpawnConfig::Https(acceptor) => {
           axum_server::Server::bind(addr)
             .handle(handle)
+            .http_config(http_config)
             .acceptor(acceptor)
             .serve(router.into_make_service())
             .await
@@ -388,6 +602,7 @@ impl Server {
         SpawnConfig::Redirect(destination) => {
           axum_server::Server::bind(addr)
             .handle(handle)
+            .http_config(http_config)
             .serve(
               Router::new()
                 .fallback(Self::redirect_http_to_https)
@@ -399,6 +614,7 @@ impl Server {
         SpawnConfig::Http => {
           axum_server::Server::bind(addr)
             .handle(handle)
+            .http_config(http_config)
             .serve(router.into_make_service())
             .await
         }
@@ -406,6 +622,69 @@ impl Server {
     }))
   }
 
+  #[cfg(unix)]
+  fn spawn_unix(
+    router: Router,
+    path: &std::path::Path,
+    shutdown: Arc<Notify>,
+  ) -> Result<task::JoinHandle<Result>> {
+    use {std::os::unix::fs::FileTypeExt, tokio::net::UnixListener};
+
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+      fs::remove_file(path)?;
+    }
+
+    let listener = UnixListener::bind(path)
+      .with_context(|| format!("failed to listen on unix socket `{}`", path.display()))?;
+
+    let path = path.to_owned();
+
+    Ok(tokio::spawn(async move {
+      let result = hyper::Server::builder(hyper::server::accept::poll_fn(move |cx| {
+        listener
+          .poll_accept(cx)
+          .map(|result| Some(result.map(|(stream, _address)| stream)))
+      }))
+      .serve(router.into_make_service())
+      .with_graceful_shutdown(shutdown.notified())
+      .await;
+
+      let removed = fs::remove_file(&path)
+        .with_context(|| format!("failed to remove unix socket `{}`", path.display()));
+
+      result?;
+      removed
+    }))
+  }
+
+  #[cfg(not(unix))]
+  fn spawn_unix(
+    _router: Router,
+    _path: &std::path::Path,
+    _shutdown: Arc<Notify>,
+  ) -> Result<task::JoinHandle<Result>> {
+    bail!("unix sockets are not supported on this platform");
+  }
+
+  fn http_config(&self) -> HttpConfig {
+    let mut config = HttpConfig::new();
+
+    config
+      .http1_only(self.http1_only)
+      .http1_keep_alive(!self.disable_keep_alive)
+      .http2_keep_alive_interval(self.http2_keep_alive_interval.map(Into::into));
+
+    if let Some(timeout) = self.http2_keep_alive_timeout {
+      config.http2_keep_alive_timeout(timeout.into());
+    }
+
+    if let Some(max) = self.http2_max_concurrent_streams {
+      config.http2_max_concurrent_streams(max);
+    }
+
+    config.build()
+  }
+
   fn acme_cache(acme_cache: Option<&PathBuf>, settings: &Settings) -> PathBuf {
     match acme_cache {
       Some(acme_cache) => acme_cache.clone(),
@@ -478,6 +757,49 @@ impl Server {
     index.block_height()?.ok_or_not_found(|| "genesis block")
   }
 
+  /// Look up an output, sharing the result with concurrent lookups of the same
+  /// output at the same height.
+  fn output_info(
+    index: &Index,
+    output_lookups: &OutputLookups,
+    rtx: &crate::index::rtx::Rtx,
+    outpoint: OutPoint,
+  ) -> Result<Option<(api::Output, TxOut)>> {
+    output_lookups
+      .run(
+        (outpoint, rtx.block_height()?.map(|height| height.n())),
+        || index.get_output_info(rtx, outpoint).map_err(Arc::new),
+      )
+      .map_err(|err| anyhow!("{err:#}"))
+  }
+
+  /// Whether any entity tag in the request's `If-None-Match` list matches
+  /// `etag`, using the weak comparison that RFC 9110 requires for this header.
+  fn if_none_match(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+      return false;
+    };
+
+    headers
+      .get_all(header::IF_NONE_MATCH)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .flat_map(|value| value.split(','))
+      .map(str::trim)
+      .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+  }
+
+  /// Report the height of the index snapshot that a response was read from.
+  fn with_snapshot_height(mut response: Response, height: Option<Height>) -> Response {
+    if let Some(height) = height {
+      response
+        .headers_mut()
+        .insert(SNAPSHOT_HEIGHT, HeaderValue::from(height.n()));
+    }
+
+    response
+  }
+
   async fn clock(Extension(index): Extension<Arc<Index>>) -> ServerResult {
     task::block_in_place(|| {
       Ok(
@@ -577,16 +899,18 @@ impl Server {
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(output_lookups): Extension<Arc<OutputLookups>>,
     Path(outpoint): Path<OutPoint>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (output_info, txout) = index
-        .get_output_info(outpoint)?
+      let rtx = index.begin_read()?;
+
+      let (output_info, txout) = Self::output_info(&index, &output_lookups, &rtx, outpoint)?
         .ok_or_not_found(|| format!("output {outpoint}"))?;
 
       Ok(if accept_json {
-        Json(output_info).into_response()
+        Self::with_snapshot_height(Json(output_info).into_response(), rtx.block_height()?)
       } else {
         OutputHtml {
           chain: server_config.chain,
@@ -604,21 +928,84 @@ impl Server {
   }
 
   async fn outputs(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(output_lookups): Extension<Arc<OutputLookups>>,
     _: AcceptJson,
+    AcceptNdjson(accept_ndjson): AcceptNdjson,
     Json(outputs): Json<Vec<OutPoint>>,
   ) -> ServerResult {
-    task::block_in_place(|| {
-      let mut response = Vec::new();
-      for outpoint in outputs {
-        let (output_info, _) = index
-          .get_output_info(outpoint)?
-          .ok_or_not_found(|| format!("output {outpoint}"))?;
+    let (rtx, height) = task::block_in_place(|| {
+      let rtx = index.begin_read()?;
+      let height = rtx.block_height()?;
+      Ok::<_, Error>((Arc::new(rtx), height))
+    })?;
+
+    let output_infos = futures::StreamExt::buffered(
+      tokio_stream::iter(outputs).map(move |outpoint| {
+        let index = index.clone();
+        let output_lookups = output_lookups.clone();
+        let rtx = rtx.clone();
+        async move {
+          let output_info = task::spawn_blocking(move || {
+            Self::output_info(&index, &output_lookups, &rtx, outpoint)
+          })
+          .await
+          .map_err(Error::from)
+          .and_then(|output_info| output_info);
 
-        response.push(output_info);
-      }
-      Ok(Json(response).into_response())
-    })
+          (
+            outpoint,
+            output_info.map(|output_info| output_info.map(|(output_info, _)| output_info)),
+          )
+        }
+      }),
+      server_config.output_lookup_concurrency,
+    );
+
+    if accept_ndjson {
+      // the status has been sent by the time a lookup fails, so errors are
+      // reported in place of the output's line
+      let lines = output_infos.map(|(outpoint, output_info)| {
+        let mut line = match output_info {
+          Ok(Some(output_info)) => serde_json::to_vec(&output_info)?,
+          Ok(None) => serde_json::to_vec(&api::OutputError {
+            error: format!("output {outpoint} not found"),
+            outpoint,
+          })?,
+          Err(err) => serde_json::to_vec(&api::OutputError {
+            error: err.to_string(),
+            outpoint,
+          })?,
+        };
+
+        line.push(b'\n');
+
+        Ok::<Vec<u8>, Error>(line)
+      });
+
+      return Ok(Self::with_snapshot_height(
+        (
+          [(header::CONTENT_TYPE, "application/x-ndjson")],
+          body::StreamBody::new(lines),
+        )
+          .into_response(),
+        height,
+      ));
+    }
+
+    let mut response = Vec::new();
+
+    tokio::pin!(output_infos);
+
+    while let Some((outpoint, output_info)) = output_infos.next().await {
+      response.push(output_info?.ok_or_not_found(|| format!("output {outpoint}"))?);
+    }
+
+    Ok(Self::with_snapshot_height(
+      Json(response).into_response(),
+      height,
+    ))
   }
 
   async fn range(
@@ -641,6 +1028,52 @@ impl Server {
     task::block_in_place(|| Ok(RareTxt(index.rare_sat_satpoints()?)))
   }
 
+  async fn rare_sats(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path(DeserializeFromStr(rarity)): Path<DeserializeFromStr<Rarity>>,
+  ) -> ServerResult<Json<api::SatMatches>> {
+    Self::rare_sats_paginated(
+      Extension(index),
+      Extension(scan_pool),
+      Path((DeserializeFromStr(rarity), 0)),
+    )
+    .await
+  }
+
+  async fn rare_sats_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path((DeserializeFromStr(rarity), page)): Path<(DeserializeFromStr<Rarity>, usize)>,
+  ) -> ServerResult<Json<api::SatMatches>> {
+    scan_pool
+      .run(move |cancelled| {
+        if !index.has_sat_index() {
+          return Err(ServerError::NotFound(
+            "this server has no sat index".to_string(),
+          ));
+        }
+
+        if rarity == Rarity::Common {
+          return Err(ServerError::BadRequest(
+            "common sats are not indexed".to_string(),
+          ));
+        }
+
+        let (sats, more) = index.get_rare_sat_satpoints_paginated(rarity, 100, page, cancelled)?;
+
+        Ok(Json(api::SatMatches {
+          more,
+          page,
+          sats: sats
+            .into_iter()
+            .map(|(sat, satpoint)| api::SatMatch { sat, satpoint })
+            .collect(),
+        }))
+      })
+      .await
+  }
+
   async fn rune(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -904,6 +1337,75 @@ impl Server {
     })
   }
 
+  fn compression(&self) -> CompressionLayer {
+    let compression = CompressionLayer::new()
+      .gzip(!self.disable_compression)
+      .br(!self.disable_compression);
+
+    match self.compression_level {
+      Some(level) => compression.quality(CompressionLevel::Precise(level)),
+      None => compression,
+    }
+  }
+
+  fn cors(&self) -> Result<CorsLayer> {
+    let cors = CorsLayer::new().allow_methods([http::Method::GET, http::Method::POST]);
+
+    let cors = if self.cors_origin.is_empty() {
+      cors.allow_origin(Any)
+    } else {
+      cors.allow_origin(AllowOrigin::list(
+        self
+          .cors_origin
+          .iter()
+          .map(|origin| {
+            HeaderValue::from_str(origin).with_context(|| format!("invalid CORS origin `{origin}`"))
+          })
+          .collect::<Result<Vec<HeaderValue>>>()?,
+      ))
+    };
+
+    let cors = cors.allow_headers(
+      self
+        .cors_header
+        .iter()
+        .map(|header| {
+          header::HeaderName::from_str(header)
+            .with_context(|| format!("invalid CORS header `{header}`"))
+        })
+        .collect::<Result<Vec<header::HeaderName>>>()?,
+    );
+
+    Ok(match self.cors_max_age {
+      Some(max_age) => cors.max_age(*max_age),
+      None => cors,
+    })
+  }
+
+  async fn restrict_routes<B>(
+    State(listener): State<Arc<Listener>>,
+    request: http::Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    if listener.allows(request.uri().path()) {
+      next.run(request).await
+    } else {
+      StatusCode::NOT_FOUND.into_response()
+    }
+  }
+
+  async fn limit_concurrency<B>(
+    State(semaphore): State<Arc<Semaphore>>,
+    request: http::Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let Ok(_permit) = semaphore.try_acquire() else {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    };
+
+    next.run(request).await
+  }
+
   async fn update(
     Extension(settings): Extension<Arc<Settings>>,
     Extension(index): Extension<Arc<Index>>,
@@ -989,16 +1491,49 @@ impl Server {
     })
   }
 
+  async fn inscription_location_at_height(
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, height)): Path<(InscriptionId, u32)>,
+  ) -> ServerResult<Json<api::InscriptionLocation>> {
+    task::block_in_place(|| {
+      if !index.has_inscription_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription history index".to_string(),
+        ));
+      }
+
+      let satpoint = index
+        .get_inscription_satpoint_at_height(inscription_id, height)?
+        .ok_or_not_found(|| format!("inscription {inscription_id} at height {height}"))?;
+
+      let address = index
+        .get_address_for_satpoint(satpoint)?
+        .map(|address| address.to_string());
+
+      Ok(Json(api::InscriptionLocation {
+        address,
+        height,
+        satpoint,
+      }))
+    })
+  }
+
   async fn status(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
+      let status = api::Status {
+        scan_pool: scan_pool.status(),
+        ..index.status()?
+      };
+
       Ok(if accept_json {
-        Json(index.status()?).into_response()
+        Json(status).into_response()
       } else {
-        index.status()?.page(server_config).into_response()
+        status.page(server_config).into_response()
       })
     })
   }
@@ -1171,6 +1706,23 @@ impl Server {
     })
   }
 
+  async fn block_at_time(
+    Extension(index): Extension<Arc<Index>>,
+    Path(timestamp): Path<u32>,
+  ) -> ServerResult<Json<api::BlockAtTime>> {
+    task::block_in_place(|| {
+      let (height, header) = index
+        .block_at_time(timestamp)?
+        .ok_or_not_found(|| "block")?;
+
+      Ok(Json(api::BlockAtTime {
+        hash: header.block_hash(),
+        height,
+        timestamp: header.time,
+      }))
+    })
+  }
+
   async fn block_hash_from_height_json(
     Extension(index): Extension<Arc<Index>>,
     Path(height): Path<u32>,
@@ -1346,6 +1898,11 @@ impl Server {
           .ok_or_not_found(|| format!("delegate {inscription_id}"))?
       }
 
+      if let Some(reason) = settings.suppression(&inscription) {
+        Self::audit_suppression(inscription_id, reason, "content");
+        return Ok(StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS.into_response());
+      }
+
       Ok(
         Self::content_response(inscription, accept_encoding, &server_config)?
           .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
@@ -1354,6 +1911,26 @@ impl Server {
     })
   }
 
+  fn audit_suppression(inscription_id: InscriptionId, reason: Suppression, route: &str) {
+    #[derive(Serialize)]
+    struct Record<'a> {
+      inscription_id: InscriptionId,
+      reason: Suppression,
+      route: &'a str,
+    }
+
+    log::warn!(
+      target: "ord::audit",
+      "{}",
+      serde_json::to_string(&Record {
+        inscription_id,
+        reason,
+        route,
+      })
+      .unwrap()
+    );
+  }
+
   fn content_response(
     inscription: Inscription,
     accept_encoding: AcceptEncoding,
@@ -1446,6 +2023,17 @@ impl Server {
           .ok_or_not_found(|| format!("delegate {inscription_id}"))?
       }
 
+      if let Some(reason) = settings.suppression(&inscription) {
+        Self::audit_suppression(inscription_id, reason, "preview");
+        return Ok(
+          (
+            StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+            PreviewUnknownHtml,
+          )
+            .into_response(),
+        );
+      }
+
       let media = inscription.media();
 
       if let Media::Iframe = media {
@@ -1852,49 +2440,459 @@ impl Server {
     })
   }
 
-  async fn sat_inscription_at_index(
+  async fn sat_names(
     Extension(index): Extension<Arc<Index>>,
-    Path((DeserializeFromStr(sat), inscription_index)): Path<(DeserializeFromStr<Sat>, isize)>,
-  ) -> ServerResult<Json<api::SatInscription>> {
-    task::block_in_place(|| {
-      if !index.has_sat_index() {
-        return Err(ServerError::NotFound(
-          "this server has no sat index".to_string(),
-        ));
-      }
-
-      let id = index.get_inscription_id_by_sat_indexed(sat, inscription_index)?;
-
-      Ok(Json(api::SatInscription { id }))
-    })
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path(prefix): Path<String>,
+  ) -> ServerResult<Json<api::SatNames>> {
+    Self::sat_names_paginated(Extension(index), Extension(scan_pool), Path((prefix, 0))).await
   }
 
-  async fn redirect_http_to_https(
-    Extension(mut destination): Extension<String>,
-    uri: Uri,
-  ) -> Redirect {
-    if let Some(path_and_query) = uri.path_and_query() {
-      destination.push_str(path_and_query.as_str());
-    }
+  async fn sat_names_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path((prefix, page)): Path<(String, usize)>,
+  ) -> ServerResult<Json<api::SatNames>> {
+    scan_pool
+      .run(move |cancelled| {
+        let mut sats = Self::sats_with_name_prefix(&prefix, page.saturating_mul(100))?
+          .take(101)
+          .collect::<Vec<Sat>>();
 
-    Redirect::to(&destination)
-  }
-}
+        let more = sats.len() > 100;
 
-#[cfg(test)]
-mod tests {
-  use {
-    super::*, reqwest::Url, serde::de::DeserializeOwned, std::net::TcpListener, tempfile::TempDir,
-  };
+        sats.truncate(100);
 
-  const RUNE: u128 = 99246114928149462;
+        let satpoints = if index.has_sat_index() {
+          index.find_many(&sats, cancelled)?
+        } else {
+          vec![None; sats.len()]
+        };
 
-  #[derive(Default)]
-  struct Builder {
-    core: Option<mockcore::Handle>,
-    config: String,
-    ord_args: BTreeMap<String, Option<String>>,
-    server_args: BTreeMap<String, Option<String>>,
+        Ok(Json(api::SatNames {
+          more,
+          page,
+          sats: sats
+            .into_iter()
+            .zip(satpoints)
+            .map(|(sat, satpoint)| api::SatName {
+              name: sat.name(),
+              sat,
+              satpoint,
+            })
+            .collect(),
+        }))
+      })
+      .await
+  }
+
+  async fn sat_pattern(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path((pattern, range)): Path<(String, String)>,
+  ) -> ServerResult<Json<api::SatMatches>> {
+    Self::sat_pattern_paginated(
+      Extension(index),
+      Extension(scan_pool),
+      Path((pattern, range, 0)),
+    )
+    .await
+  }
+
+  async fn sat_pattern_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path((pattern, range, page)): Path<(String, String, usize)>,
+  ) -> ServerResult<Json<api::SatMatches>> {
+    scan_pool
+      .run(move |cancelled| {
+        let pattern = pattern
+          .parse::<SatPattern>()
+          .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+        let mut sats = pattern
+          .matches(Self::sat_pattern_range(&range)?)
+          .take_while(|_| !cancelled.load(atomic::Ordering::Relaxed))
+          .skip(page.saturating_mul(100))
+          .take(101)
+          .collect::<Vec<Sat>>();
+
+        let more = sats.len() > 100;
+
+        sats.truncate(100);
+
+        let satpoints = if index.has_sat_index() {
+          index.find_many(&sats, cancelled)?
+        } else {
+          vec![None; sats.len()]
+        };
+
+        Ok(Json(api::SatMatches {
+          more,
+          page,
+          sats: sats
+            .into_iter()
+            .zip(satpoints)
+            .map(|(sat, satpoint)| api::SatMatch { sat, satpoint })
+            .collect(),
+        }))
+      })
+      .await
+  }
+
+  /// Parses either a block height, standing for the sats mined in that block,
+  /// or a half-open sat range `<START>-<END>`.
+  fn sat_pattern_range(range: &str) -> ServerResult<std::ops::Range<u64>> {
+    let invalid = || ServerError::BadRequest(format!("invalid sat range `{range}`"));
+
+    let range = match range.split_once('-') {
+      Some((start, end)) => {
+        start.parse().map_err(|_| invalid())?..end.parse().map_err(|_| invalid())?
+      }
+      None => {
+        let height = Height(range.parse().map_err(|_| invalid())?);
+        height.starting_sat().n()..(height + 1).starting_sat().n()
+      }
+    };
+
+    if range.start >= range.end || range.end > Sat::SUPPLY {
+      return Err(invalid());
+    }
+
+    Ok(range)
+  }
+
+  /// Sats whose names start with `prefix`, in ascending order, after skipping
+  /// the first `skip`. Appending a letter to a name multiplies its value by 26
+  /// and adds 1 to 26, so names of each length form a contiguous range of
+  /// values, and longer names belong to lower sats.
+  fn sats_with_name_prefix(prefix: &str, skip: usize) -> ServerResult<impl Iterator<Item = Sat>> {
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_lowercase()) {
+      return Err(ServerError::BadRequest(format!(
+        "invalid sat name prefix `{prefix}`"
+      )));
+    }
+
+    let value = Sat::SUPPLY
+      - prefix
+        .parse::<Sat>()
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?
+        .n();
+
+    let mut ranges = Vec::new();
+    let (mut low, mut high) = (value, value);
+
+    while low <= Sat::SUPPLY {
+      ranges.push((low, high.min(Sat::SUPPLY)));
+      low = low * 26 + 1;
+      high = high * 26 + 26;
+    }
+
+    let mut skip = u64::try_from(skip).unwrap_or(u64::MAX);
+
+    Ok(
+      ranges
+        .into_iter()
+        .rev()
+        .filter_map(move |(low, high)| {
+          let len = high - low + 1;
+          if skip >= len {
+            skip -= len;
+            None
+          } else {
+            let high = high - skip;
+            skip = 0;
+            Some((low, high))
+          }
+        })
+        .flat_map(|(low, high)| (low..=high).rev())
+        .map(|value| Sat(Sat::SUPPLY - value)),
+    )
+  }
+
+  async fn sat_inscription_at_index(
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(sat), inscription_index)): Path<(DeserializeFromStr<Sat>, isize)>,
+  ) -> ServerResult<Json<api::SatInscription>> {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      let id = index.get_inscription_id_by_sat_indexed(sat, inscription_index)?;
+
+      Ok(Json(api::SatInscription { id }))
+    })
+  }
+
+  async fn sat_rarities(
+    Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
+  ) -> Json<api::SatRarities> {
+    Json(api::SatRarities {
+      charms: Charm::charms(sat.charms()),
+      rarity: sat.rarity(),
+    })
+  }
+
+  async fn utxo_ranges(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(outpoint): Path<OutPoint>,
+    query: Query<SatRangesQuery>,
+  ) -> ServerResult<Json<api::SatRanges>> {
+    Self::utxo_ranges_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((outpoint, 0)),
+      query,
+    )
+    .await
+  }
+
+  async fn utxo_ranges_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((outpoint, page)): Path<(OutPoint, usize)>,
+    Query(query): Query<SatRangesQuery>,
+  ) -> ServerResult<Json<api::SatRanges>> {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      let rtx = index.begin_read()?;
+
+      let sat_ranges = rtx
+        .list(outpoint)?
+        .ok_or_not_found(|| format!("output {outpoint}"))?;
+
+      let start = page.saturating_mul(1000);
+
+      let ranges = sat_ranges
+        .iter()
+        .skip(start)
+        .take(1000)
+        .copied()
+        .collect::<Vec<(u64, u64)>>();
+
+      let more = sat_ranges.len() > start.saturating_add(1000);
+
+      let inscriptions = if query.include_inscriptions {
+        let mut inscriptions = ranges.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+
+        // offset within the output of the first sat of each range on this page
+        let mut range_offsets = Vec::with_capacity(ranges.len());
+        let mut offset = sat_ranges
+          .iter()
+          .take(start)
+          .map(|(start, end)| end - start)
+          .sum::<u64>();
+        for (start, end) in &ranges {
+          range_offsets.push(offset);
+          offset += end - start;
+        }
+
+        for (satpoint, inscription_id) in rtx.inscriptions_on_output(outpoint)? {
+          let Some(i) = range_offsets
+            .partition_point(|&range_offset| range_offset <= satpoint.offset)
+            .checked_sub(1)
+          else {
+            continue;
+          };
+
+          let (start, end) = ranges[i];
+
+          if satpoint.offset - range_offsets[i] >= end - start {
+            continue;
+          }
+
+          let entry = rtx
+            .inscription_entry(inscription_id)?
+            .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+          inscriptions[i].push(api::SatRangeInscription {
+            charms: Charm::charms(entry.charms),
+            id: inscription_id,
+            sat: entry.sat,
+          });
+        }
+
+        Some(inscriptions)
+      } else {
+        None
+      };
+
+      // an output's sat ranges always add up to its value
+      let value = sat_ranges.iter().map(|(start, end)| end - start).sum();
+
+      let script_pubkey = if outpoint == OutPoint::null() || outpoint == unbound_outpoint() {
+        None
+      } else {
+        index
+          .get_transaction(outpoint.txid)?
+          .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.into_usize()))
+          .map(|tx_out| tx_out.script_pubkey)
+      };
+
+      Ok(Json(api::SatRanges {
+        address: script_pubkey.as_ref().and_then(|script_pubkey| {
+          server_config
+            .chain
+            .address_from_script(script_pubkey)
+            .ok()
+            .map(|address| uncheck(&address))
+        }),
+        inscriptions,
+        more,
+        page,
+        ranges,
+        script_pubkey: script_pubkey.map(|script_pubkey| script_pubkey.to_asm_string()),
+        value,
+      }))
+    })
+  }
+
+  async fn address_rarities(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(scan_pool): Extension<Arc<ScanPool>>,
+    Path(address): Path<String>,
+  ) -> ServerResult {
+    scan_pool
+      .run(move |cancelled| {
+        if !index.has_sat_index() {
+          return Err(ServerError::NotFound(
+            "this server has no sat index".to_string(),
+          ));
+        }
+
+        if !index.has_address_index() {
+          return Err(ServerError::NotFound(
+            "this server has no address index".to_string(),
+          ));
+        }
+
+        let address = address
+          .parse::<Address<NetworkUnchecked>>()
+          .map_err(|err| ServerError::BadRequest(format!("invalid address: {err}")))?
+          .require_network(server_config.chain.network())
+          .map_err(|err| ServerError::BadRequest(format!("invalid address: {err}")))?;
+
+        let rtx = index.begin_read()?;
+
+        let mut outputs = Vec::new();
+
+        for outpoint in rtx.address_outpoints(&address.script_pubkey())? {
+          if cancelled.load(atomic::Ordering::Relaxed) {
+            return Err(anyhow!("scan cancelled").into());
+          }
+
+          let Some(sat_ranges) = rtx.list(outpoint)? else {
+            continue;
+          };
+
+          let rare_sats = api::UtxoRarities::new(sat_ranges, false).rare_sats;
+
+          if !rare_sats.is_empty() {
+            outputs.push(api::OutputRarities {
+              outpoint,
+              rare_sats,
+            });
+          }
+        }
+
+        Ok(Self::with_snapshot_height(
+          Json(api::AddressRarities { outputs }).into_response(),
+          rtx.block_height()?,
+        ))
+      })
+      .await
+  }
+
+  async fn utxo_rarities(
+    Extension(index): Extension<Arc<Index>>,
+    Path(outpoint): Path<OutPoint>,
+    headers: HeaderMap,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      let sat_ranges = index
+        .list(outpoint)?
+        .ok_or_not_found(|| format!("output {outpoint}"))?;
+
+      // sat ranges of an output never change, so only spending it can
+      // invalidate a cached response
+      let spent = index.is_output_spent(outpoint)?;
+
+      let etag = HeaderValue::from_str(&format!(
+        "\"{outpoint}-{}\"",
+        if spent { "spent" } else { "unspent" }
+      ))
+      .map_err(|err| ServerError::Internal(err.into()))?;
+
+      // a mempool or shallow spend can still be evicted or reorged out, so
+      // only a deeply buried spend makes the response immutable
+      let cache_control = HeaderValue::from_static(
+        if spent && index.is_output_spend_buried(outpoint, SPEND_BURIAL_DEPTH)? {
+          "public, max-age=1209600, immutable"
+        } else {
+          "no-cache"
+        },
+      );
+
+      let not_modified = Self::if_none_match(&headers, &etag);
+
+      let cache_headers = [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)];
+
+      if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+      }
+
+      Ok(
+        (
+          cache_headers,
+          Json(api::UtxoRarities::new(sat_ranges, spent)),
+        )
+          .into_response(),
+      )
+    })
+  }
+
+  async fn redirect_http_to_https(
+    Extension(mut destination): Extension<String>,
+    uri: Uri,
+  ) -> Redirect {
+    if let Some(path_and_query) = uri.path_and_query() {
+      destination.push_str(path_and_query.as_str());
+    }
+
+    Redirect::to(&destination)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*, reqwest::Url, serde::de::DeserializeOwned, std::net::TcpListener, tempfile::TempDir,
+  };
+
+  const RUNE: u128 = 99246114928149462;
+
+  #[derive(Default)]
+  struct Builder {
+    core: Option<mockcore::Handle>,
+    config: String,
+    ord_args: BTreeMap<String, Option<String>>,
+    server_args: BTreeMap<String, Option<String>>,
   }
 
   impl Builder {
@@ -2385,6 +3383,44 @@ mod tests {
     .is_ok());
   }
 
+  #[test]
+  fn http2_options_conflict_with_http1_only() {
+    assert!(Arguments::try_parse_from([
+      "ord",
+      "server",
+      "--http1-only",
+      "--http2-max-concurrent-streams",
+      "10"
+    ])
+    .is_err());
+
+    assert!(Arguments::try_parse_from([
+      "ord",
+      "server",
+      "--http1-only",
+      "--http2-keep-alive-interval",
+      "10s"
+    ])
+    .is_err());
+  }
+
+  #[test]
+  fn http2_keep_alive_timeout_requires_interval() {
+    assert!(
+      Arguments::try_parse_from(["ord", "server", "--http2-keep-alive-timeout", "10s"]).is_err()
+    );
+
+    assert!(Arguments::try_parse_from([
+      "ord",
+      "server",
+      "--http2-keep-alive-interval",
+      "30s",
+      "--http2-keep-alive-timeout",
+      "10s"
+    ])
+    .is_ok());
+  }
+
   #[test]
   fn acme_cache_defaults_to_data_dir() {
     let arguments = Arguments::try_parse_from(["ord", "--datadir", "foo", "server"]).unwrap();
@@ -3316,6 +4352,8 @@ mod tests {
   <dd>false</dd>
   <dt>transaction index</dt>
   <dd>false</dd>
+  <dt>scan threads</dt>
+  <dd>0 of 2 active, 0 queued, 0 completed</dd>
   <dt>git branch</dt>
   <dd>.*</dd>
   <dt>git commit</dt>
@@ -3355,6 +4393,70 @@ mod tests {
     assert_eq!(response.text().unwrap(), "2");
   }
 
+  #[test]
+  fn listener_only_serves_routes_with_given_prefixes() {
+    let port = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+    let _test_server = TestServer::builder()
+      .server_option("--listener", &format!("{port}=/r/,/status"))
+      .build();
+
+    let get = |path: &str| {
+      reqwest::blocking::get(format!("http://127.0.0.1:{port}{path}"))
+        .unwrap()
+        .status()
+    };
+
+    assert_eq!(get("/r/blockheight"), StatusCode::OK);
+    assert_eq!(get("/status"), StatusCode::OK);
+    assert_eq!(get("/blockcount"), StatusCode::NOT_FOUND);
+    assert_eq!(get("/"), StatusCode::NOT_FOUND);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn unix_socket_listener() {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().join("ord.sock");
+
+    let test_server = TestServer::builder()
+      .server_option("--unix-socket", path.to_str().unwrap())
+      .build();
+
+    let mut stream = UnixStream::connect(&path).unwrap();
+
+    stream
+      .write_all(b"GET /blockcount HTTP/1.0\r\nHost: localhost\r\n\r\n")
+      .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    assert!(response.starts_with("HTTP/1.0 200 OK\r\n"), "{response}");
+    assert!(response.ends_with("\r\n\r\n1"), "{response}");
+
+    drop(test_server);
+
+    for attempt in 0.. {
+      if !path.exists() {
+        break;
+      }
+
+      if attempt == 100 {
+        panic!("unix socket was not removed on shutdown");
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+  }
+
   #[test]
   fn block_height_endpoint() {
     let test_server = TestServer::new();
@@ -3915,10 +5017,137 @@ mod tests {
   }
 
   #[test]
-  fn rare_without_sat_index() {
-    TestServer::new().assert_response(
-      "/rare.txt",
-      StatusCode::OK,
+  fn rare_sats_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(101);
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/raresats/mythic"),
+      api::SatMatches {
+        more: false,
+        page: 0,
+        sats: vec![api::SatMatch {
+          sat: Sat(0),
+          satpoint: Some(SatPoint {
+            outpoint: Chain::Regtest.genesis_coinbase_outpoint(),
+            offset: 0,
+          }),
+        }],
+      }
+    );
+
+    let uncommon = |height: usize| api::SatMatch {
+      sat: Sat(u64::try_from(height).unwrap() * 50 * COIN_VALUE),
+      satpoint: Some(SatPoint {
+        outpoint: OutPoint {
+          txid: server.core.tx(height, 0).txid(),
+          vout: 0,
+        },
+        offset: 0,
+      }),
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/raresats/uncommon"),
+      api::SatMatches {
+        more: true,
+        page: 0,
+        sats: (1..=100).map(uncommon).collect(),
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/raresats/uncommon/1"),
+      api::SatMatches {
+        more: false,
+        page: 1,
+        sats: vec![uncommon(101)],
+      }
+    );
+
+    server.assert_response(
+      "/r/raresats/common",
+      StatusCode::BAD_REQUEST,
+      "common sats are not indexed",
+    );
+  }
+
+  #[test]
+  fn rare_sats_recursive_endpoint_with_untracked_rarity() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .ord_option("--untracked-rarities", "uncommon")
+      .build();
+
+    server.mine_blocks(101);
+
+    assert_eq!(
+      server
+        .index
+        .rare_sat_satpoints()
+        .unwrap()
+        .into_iter()
+        .map(|(sat, _satpoint)| sat)
+        .collect::<Vec<Sat>>(),
+      [Sat(0)],
+    );
+
+    let uncommon = |height: usize| api::SatMatch {
+      sat: Sat(u64::try_from(height).unwrap() * 50 * COIN_VALUE),
+      satpoint: Some(SatPoint {
+        outpoint: OutPoint {
+          txid: server.core.tx(height, 0).txid(),
+          vout: 0,
+        },
+        offset: 0,
+      }),
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/raresats/uncommon"),
+      api::SatMatches {
+        more: true,
+        page: 0,
+        sats: (1..=100).map(uncommon).collect(),
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/raresats/uncommon/1"),
+      api::SatMatches {
+        more: false,
+        page: 1,
+        sats: vec![uncommon(101)],
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Sat>(format!("/sat/{}", 50 * COIN_VALUE))
+        .satpoint,
+      uncommon(1).satpoint,
+    );
+  }
+
+  #[test]
+  fn rare_sats_recursive_endpoint_requires_sat_index() {
+    TestServer::new().assert_response(
+      "/r/raresats/uncommon",
+      StatusCode::NOT_FOUND,
+      "this server has no sat index",
+    );
+  }
+
+  #[test]
+  fn rare_without_sat_index() {
+    TestServer::new().assert_response(
+      "/rare.txt",
+      StatusCode::OK,
       "sat\tsatpoint
 ",
     );
@@ -4915,6 +6144,64 @@ next
     );
   }
 
+  #[test]
+  fn responses_are_not_compressed_when_compression_is_disabled() {
+    let server = TestServer::builder()
+      .server_flag("--disable-compression")
+      .build();
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_ENCODING, "gzip, br".parse().unwrap());
+
+    let response = reqwest::blocking::Client::builder()
+      .default_headers(headers)
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url("/"))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+  }
+
+  #[test]
+  fn compressed_request_bodies_are_accepted() {
+    let mut compressor = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+    std::io::Write::write_all(
+      &mut compressor,
+      &serde_json::to_vec(&[OutPoint::null()]).unwrap(),
+    )
+    .unwrap();
+    let body = compressor.into_inner();
+
+    for (flag, status) in [
+      (false, StatusCode::UNSUPPORTED_MEDIA_TYPE),
+      (true, StatusCode::OK),
+    ] {
+      let mut builder = TestServer::builder().chain(Chain::Regtest);
+
+      if flag {
+        builder = builder.server_flag("--accept-compressed-requests");
+      }
+
+      let server = builder.build();
+
+      let response = reqwest::blocking::Client::new()
+        .post(server.join_url("/outputs"))
+        .header(header::ACCEPT, "application/json")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_ENCODING, "br")
+        .body(body.clone())
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), status);
+    }
+  }
+
   #[test]
   fn inscription_links_to_parent() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -5882,122 +7169,1174 @@ next
   }
 
   #[test]
-  fn children_recursive_endpoint() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn sat_rarities_recursive_endpoint() {
+    let server = TestServer::new();
+
+    assert_eq!(
+      server.get_json::<api::SatRarities>("/r/sat/0/rarities"),
+      api::SatRarities {
+        charms: vec![Charm::Coin, Charm::Mythic],
+        rarity: Rarity::Mythic,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::SatRarities>("/r/sat/5000000000/rarities"),
+      api::SatRarities {
+        charms: vec![Charm::Coin, Charm::Uncommon],
+        rarity: Rarity::Uncommon,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::SatRarities>("/r/sat/5000000001/rarities"),
+      api::SatRarities {
+        charms: Vec::new(),
+        rarity: Rarity::Common,
+      }
+    );
+  }
+
+  #[test]
+  fn inscription_location_at_height_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-history")
+      .build();
+
     server.mine_blocks(1);
 
-    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    let create_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
       ..default()
     });
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
-    };
+    server.mine_blocks(1);
 
-    server.assert_response(
-      format!("/r/children/{parent_inscription_id}"),
-      StatusCode::NOT_FOUND,
-      &format!("inscription {parent_inscription_id} not found"),
-    );
+    let transfer_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
 
     server.mine_blocks(1);
 
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
-    assert_eq!(children_json.ids.len(), 0);
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
 
-    let mut builder = script::Builder::new();
-    for _ in 0..111 {
-      builder = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        parents: vec![parent_inscription_id.value()],
-        unrecognized_even_field: false,
-        ..default()
-      }
-      .append_reveal_script_to_builder(builder);
+    server.assert_response(
+      format!("/r/inscription/{inscription_id}/at/1"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} at height 1 not found"),
+    );
+
+    for (height, txid) in [(2, create_txid), (3, transfer_txid), (4, transfer_txid)] {
+      let outpoint = OutPoint { txid, vout: 0 };
+
+      pretty_assert_eq!(
+        server.get_json::<api::InscriptionLocation>(format!(
+          "/r/inscription/{inscription_id}/at/{height}"
+        )),
+        api::InscriptionLocation {
+          address: server
+            .get_json::<api::Output>(format!("/output/{outpoint}"))
+            .address
+            .map(|address| address.assume_checked().to_string()),
+          height,
+          satpoint: SatPoint {
+            outpoint,
+            offset: 0,
+          },
+        }
+      );
     }
+  }
 
-    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+  #[test]
+  fn inscription_location_at_height_requires_inscription_history_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let first_child_inscription_id = InscriptionId { txid, index: 0 };
-    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
-    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
-    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+    server.assert_response(
+      format!("/r/inscription/{}/at/2", InscriptionId { txid, index: 0 }),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+  }
 
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
+  #[test]
+  fn max_body_size_is_configurable() {
+    let outpoints = vec![OutPoint::null(); 100];
 
-    assert_eq!(children_json.ids.len(), 100);
-    assert_eq!(children_json.ids[0], first_child_inscription_id);
-    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
-    assert!(children_json.more);
-    assert_eq!(children_json.page, 0);
+    for (max_body_size, status) in [
+      ("1024", StatusCode::PAYLOAD_TOO_LARGE),
+      ("1048576", StatusCode::OK),
+    ] {
+      let server = TestServer::builder()
+        .chain(Chain::Regtest)
+        .server_flag("--limit-requests")
+        .server_option("--max-body-size", max_body_size)
+        .server_flag("--disable-keep-alive")
+        .build();
 
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}/1"));
+      let response = reqwest::blocking::Client::new()
+        .post(server.join_url("/outputs"))
+        .header(header::ACCEPT, "application/json")
+        .json(&outpoints)
+        .send()
+        .unwrap();
 
-    assert_eq!(children_json.ids.len(), 11);
-    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
-    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
-    assert!(!children_json.more);
-    assert_eq!(children_json.page, 1);
+      assert_eq!(response.status(), status);
+    }
   }
 
   #[test]
-  fn inscriptions_in_block_page() {
+  fn cors_preflight_is_configurable() {
     let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
+      .server_option("--cors-origin", "https://example.com")
+      .server_option("--cors-header", "content-type")
+      .server_option("--cors-max-age", "10m")
       .build();
 
-    for _ in 0..101 {
-      server.mine_blocks(1);
-    }
-
-    for i in 0..101 {
-      server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..default()
-      });
-    }
+    let preflight = |origin: &str| {
+      reqwest::blocking::Client::new()
+        .request(reqwest::Method::OPTIONS, server.join_url("/outputs"))
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+        .send()
+        .unwrap()
+    };
 
-    server.mine_blocks(1);
+    let response = preflight("https://example.com");
 
-    server.assert_response_regex(
-      "/inscriptions/block/102",
-      StatusCode::OK,
-      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+      "https://example.com"
     );
-
-    server.assert_response_regex(
-      "/inscriptions/block/102/1",
-      StatusCode::OK,
-      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
+    assert_eq!(
+      response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS],
+      "content-type"
     );
+    assert_eq!(response.headers()[header::ACCESS_CONTROL_MAX_AGE], "600");
+
+    assert!(!preflight("https://example.org")
+      .headers()
+      .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
   }
 
   #[test]
-  fn inscription_query_display() {
-    assert_eq!(
-      query::Inscription::Id(inscription_id(1)).to_string(),
-      "1111111111111111111111111111111111111111111111111111111111111111i1"
-    );
-    assert_eq!(query::Inscription::Number(1).to_string(), "1")
+  fn limit_requests_caps_request_body_size() {
+    let outpoints = vec![OutPoint::null(); 2000];
+
+    for (flag, status) in [
+      (false, StatusCode::OK),
+      (true, StatusCode::PAYLOAD_TOO_LARGE),
+    ] {
+      let mut builder = TestServer::builder().chain(Chain::Regtest);
+
+      if flag {
+        builder = builder.server_flag("--limit-requests");
+      }
+
+      let server = builder.build();
+
+      let response = reqwest::blocking::Client::new()
+        .post(server.join_url("/outputs"))
+        .header(header::ACCEPT, "application/json")
+        .json(&outpoints)
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), status);
+    }
   }
 
   #[test]
-  fn inscription_not_found() {
-    TestServer::builder()
+  fn outputs_can_be_streamed_as_ndjson() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let outpoints = server
+      .mine_blocks(2)
+      .iter()
+      .map(|block| OutPoint {
+        txid: block.txdata[0].txid(),
+        vout: 0,
+      })
+      .collect::<Vec<OutPoint>>();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/outputs"))
+      .header(header::ACCEPT, "application/x-ndjson")
+      .json(&outpoints)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "application/x-ndjson"
+    );
+
+    let text = response.text().unwrap();
+
+    let lines = text
+      .lines()
+      .map(|line| serde_json::from_str::<api::Output>(line).unwrap())
+      .collect::<Vec<api::Output>>();
+
+    assert_eq!(lines.len(), 2);
+
+    for (line, outpoint) in lines.into_iter().zip(outpoints) {
+      pretty_assert_eq!(
+        line,
+        server.get_json::<api::Output>(format!("/output/{outpoint}"))
+      );
+    }
+  }
+
+  #[test]
+  fn missing_outputs_are_streamed_as_ndjson_errors() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    let missing = OutPoint {
+      txid: outpoint.txid,
+      vout: 1,
+    };
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/outputs"))
+      .header(
+        header::ACCEPT,
+        "application/json;q=0.9, application/x-ndjson; charset=utf-8",
+      )
+      .json(&[missing, outpoint])
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "application/x-ndjson"
+    );
+
+    let text = response.text().unwrap();
+    let mut lines = text.lines();
+
+    pretty_assert_eq!(
+      serde_json::from_str::<api::OutputError>(lines.next().unwrap()).unwrap(),
+      api::OutputError {
+        error: format!("output {missing} not found"),
+        outpoint: missing,
+      }
+    );
+
+    pretty_assert_eq!(
+      serde_json::from_str::<api::Output>(lines.next().unwrap()).unwrap(),
+      server.get_json::<api::Output>(format!("/output/{outpoint}"))
+    );
+
+    assert_eq!(lines.next(), None);
+  }
+
+  #[test]
+  fn output_responses_report_snapshot_height() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(2)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/output/{outpoint}")))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(SNAPSHOT_HEIGHT).unwrap(), "2");
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/outputs"))
+      .header(header::ACCEPT, "application/json")
+      .json(&[outpoint])
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(SNAPSHOT_HEIGHT).unwrap(), "2");
+  }
+
+  #[test]
+  fn outputs_are_looked_up_concurrently_in_order() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .server_option("--output-lookup-concurrency", "4")
+      .build();
+
+    let mut outpoints = server
+      .mine_blocks(10)
+      .iter()
+      .map(|block| OutPoint {
+        txid: block.txdata[0].txid(),
+        vout: 0,
+      })
+      .collect::<Vec<OutPoint>>();
+
+    outpoints.reverse();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/outputs"))
+      .header(header::ACCEPT, "application/json")
+      .json(&outpoints)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    pretty_assert_eq!(
+      response.json::<Vec<api::Output>>().unwrap(),
+      outpoints
+        .iter()
+        .map(|outpoint| server.get_json::<api::Output>(format!("/output/{outpoint}")))
+        .collect::<Vec<api::Output>>(),
+    );
+
+    outpoints.push(OutPoint {
+      txid: txid(1),
+      vout: 0,
+    });
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/outputs"))
+      .header(header::ACCEPT, "application/json")
+      .json(&outpoints)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn utxo_rarities_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    let response = server.get(format!("/r/utxo/{outpoint}/rarities"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let etag = response.headers().get(header::ETAG).unwrap().clone();
+
+    assert_eq!(etag, format!("\"{outpoint}-unspent\"").as_str());
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-cache"
+    );
+
+    pretty_assert_eq!(
+      response.json::<api::UtxoRarities>().unwrap(),
+      api::UtxoRarities {
+        rare_sats: vec![api::RareSat {
+          offset: 0,
+          rarity: Rarity::Uncommon,
+          sat: Sat(50 * COIN_VALUE),
+        }],
+        sat_ranges: vec![(50 * COIN_VALUE, 100 * COIN_VALUE)],
+        spent: false,
+      }
+    );
+
+    let if_none_match = |value: &str| {
+      reqwest::blocking::Client::new()
+        .get(server.join_url(&format!("/r/utxo/{outpoint}/rarities")))
+        .header(header::IF_NONE_MATCH, value)
+        .send()
+        .unwrap()
+    };
+
+    assert_eq!(
+      if_none_match(etag.to_str().unwrap()).status(),
+      StatusCode::NOT_MODIFIED
+    );
+
+    assert_eq!(
+      if_none_match(&format!("\"foo\", W/{}", etag.to_str().unwrap())).status(),
+      StatusCode::NOT_MODIFIED
+    );
+
+    assert_eq!(if_none_match("*").status(), StatusCode::NOT_MODIFIED);
+
+    assert_eq!(if_none_match("\"foo\", \"bar\"").status(), StatusCode::OK);
+  }
+
+  #[test]
+  fn utxo_rarities_are_immutable_once_spend_is_buried() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .ord_flag("--index-spent-sats")
+      .build();
+
+    server.mine_blocks(1);
+
+    let outpoint = OutPoint {
+      txid: server.core.tx(1, 0).txid(),
+      vout: 0,
+    };
+
+    let cache_headers = || {
+      let response = server.get(format!("/r/utxo/{outpoint}/rarities"));
+      assert_eq!(response.status(), StatusCode::OK);
+      (
+        response
+          .headers()
+          .get(header::ETAG)
+          .unwrap()
+          .to_str()
+          .unwrap()
+          .to_string(),
+        response
+          .headers()
+          .get(header::CACHE_CONTROL)
+          .unwrap()
+          .to_str()
+          .unwrap()
+          .to_string(),
+      )
+    };
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    let spent = (format!("\"{outpoint}-spent\""), "no-cache".to_string());
+
+    assert_eq!(cache_headers(), spent);
+
+    server.mine_blocks(u64::from(SPEND_BURIAL_DEPTH) - 1);
+
+    assert_eq!(cache_headers(), spent);
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      cache_headers(),
+      (
+        format!("\"{outpoint}-spent\""),
+        "public, max-age=1209600, immutable".to_string()
+      )
+    );
+  }
+
+  #[test]
+  fn block_at_time_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let blocks = server.mine_blocks(5);
+
+    pretty_assert_eq!(
+      server.get_json::<api::BlockAtTime>("/r/blockheight/at/3"),
+      api::BlockAtTime {
+        hash: blocks[2].block_hash(),
+        height: 3,
+        timestamp: 3,
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::BlockAtTime>("/r/blockheight/at/0")
+        .height,
+      1
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::BlockAtTime>("/r/blockheight/at/100")
+        .height,
+      5
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::BlockAtTime>("/r/blockheight/at/4000000000")
+        .height,
+      0
+    );
+  }
+
+  #[test]
+  fn sat_pattern_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let coinbase = server.core.tx(1, 0).txid();
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/satpattern/repeated/1"),
+      api::SatMatches {
+        more: false,
+        page: 0,
+        sats: (5..10)
+          .map(|digit| {
+            let sat = digit * 1_111_111_111;
+            api::SatMatch {
+              sat: Sat(sat),
+              satpoint: Some(SatPoint {
+                outpoint: OutPoint {
+                  txid: coinbase,
+                  vout: 0,
+                },
+                offset: sat - 50 * COIN_VALUE,
+              }),
+            }
+          })
+          .collect(),
+      }
+    );
+
+    let response = server.get_json::<api::SatMatches>("/r/satpattern/palindrome/0-1000");
+    assert!(response.more);
+    assert_eq!(response.sats.len(), 100);
+    assert_eq!(response.sats[99].sat, Sat(909));
+
+    let response = server.get_json::<api::SatMatches>("/r/satpattern/palindrome/0-1000/1");
+    assert!(!response.more);
+    assert_eq!(
+      response
+        .sats
+        .iter()
+        .map(|sat| sat.sat.n())
+        .collect::<Vec<u64>>(),
+      [919, 929, 939, 949, 959, 969, 979, 989, 999],
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatMatches>("/r/satpattern/contains:1234567890/1"),
+      api::SatMatches {
+        more: false,
+        page: 0,
+        sats: Vec::new(),
+      }
+    );
+
+    server.assert_response(
+      "/r/satpattern/foo/1",
+      StatusCode::BAD_REQUEST,
+      "unknown sat pattern `foo`",
+    );
+
+    server.assert_response(
+      "/r/satpattern/palindrome/10-5",
+      StatusCode::BAD_REQUEST,
+      "invalid sat range `10-5`",
+    );
+  }
+
+  #[test]
+  fn scans_time_out() {
+    TestServer::builder()
+      .server_option("--scan-timeout", "10ms")
+      .build()
+      .assert_response(
+        "/r/satpattern/palindrome/0-2000000000000000/1000000",
+        StatusCode::SERVICE_UNAVAILABLE,
+        "scan did not finish within the 10ms scan timeout",
+      );
+  }
+
+  #[test]
+  fn sats_with_name_prefix() {
+    assert_eq!(
+      Server::sats_with_name_prefix("nvtdijuwxlp", 0)
+        .unwrap()
+        .collect::<Vec<Sat>>(),
+      [Sat(0)],
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("nvtdijuwx", 0)
+        .unwrap()
+        .take_while(|sat| sat.n() < 100_000)
+        .collect::<Vec<Sat>>(),
+      (0..100_000)
+        .map(Sat)
+        .filter(|sat| sat.name().starts_with("nvtdijuwx"))
+        .collect::<Vec<Sat>>(),
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("zzzzzzz", 0).unwrap().count(),
+      1 + 26 + 26 * 26 + 26 * 26 * 26,
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("zzzzzzz", 0).unwrap().last(),
+      Some("zzzzzzz".parse().unwrap()),
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("zzzzzzz", 17_576)
+        .unwrap()
+        .collect::<Vec<Sat>>(),
+      Server::sats_with_name_prefix("zzzzzzz", 0)
+        .unwrap()
+        .skip(17_576)
+        .collect::<Vec<Sat>>(),
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("zzzzzzz", 17_580)
+        .unwrap()
+        .collect::<Vec<Sat>>(),
+      Server::sats_with_name_prefix("zzzzzzz", 0)
+        .unwrap()
+        .skip(17_580)
+        .collect::<Vec<Sat>>(),
+    );
+
+    assert_eq!(
+      Server::sats_with_name_prefix("zzzzzzz", usize::MAX)
+        .unwrap()
+        .next(),
+      None,
+    );
+
+    assert!(Server::sats_with_name_prefix("", 0).is_err());
+    assert!(Server::sats_with_name_prefix("A", 0).is_err());
+    assert!(Server::sats_with_name_prefix("zzzzzzzzzzzz", 0).is_err());
+  }
+
+  #[test]
+  fn sat_names_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let txid = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let sat = Sat(50 * COIN_VALUE);
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatNames>(format!("/r/satnames/{}", sat.name())),
+      api::SatNames {
+        more: false,
+        page: 0,
+        sats: vec![api::SatName {
+          name: sat.name(),
+          sat,
+          satpoint: Some(SatPoint {
+            outpoint: OutPoint { txid, vout: 0 },
+            offset: 0,
+          }),
+        }],
+      }
+    );
+
+    let names = server.get_json::<api::SatNames>("/r/satnames/zz/1");
+
+    assert!(names.more);
+    assert_eq!(names.page, 1);
+    assert_eq!(names.sats.len(), 100);
+    assert!(names
+      .sats
+      .iter()
+      .all(|sat| sat.name.starts_with("zz") && sat.satpoint.is_none()));
+
+    server.assert_response(
+      "/r/satnames/Foo",
+      StatusCode::BAD_REQUEST,
+      "invalid sat name prefix `Foo`",
+    );
+  }
+
+  #[test]
+  fn utxo_ranges_with_inscriptions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(2);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, Default::default()),
+        (
+          2,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        ),
+      ],
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let response = server.get_json::<api::SatRanges>(format!("/r/utxo/{txid}:0/ranges"));
+
+    assert_eq!(response.inscriptions, None);
+
+    let response = server
+      .get_json::<api::SatRanges>(format!("/r/utxo/{txid}:0/ranges?include_inscriptions=true"));
+
+    assert_eq!(
+      response.ranges,
+      [
+        (50 * COIN_VALUE, 100 * COIN_VALUE),
+        (100 * COIN_VALUE, 150 * COIN_VALUE)
+      ],
+    );
+
+    pretty_assert_eq!(
+      response.inscriptions,
+      Some(vec![
+        Vec::new(),
+        vec![api::SatRangeInscription {
+          charms: vec![Charm::Coin, Charm::Uncommon, Charm::Cursed],
+          id,
+          sat: Some(Sat(100 * COIN_VALUE)),
+        }],
+      ]),
+    );
+  }
+
+  #[test]
+  fn utxo_ranges_are_paginated() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1001);
+
+    let inputs = (1..=1001)
+      .map(|height| (height, 0, 0, Default::default()))
+      .collect::<Vec<(usize, usize, usize, Witness)>>();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &inputs,
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let script_pubkey = server.core.tx_by_id(txid).output[0].script_pubkey.clone();
+
+    let address = Some(uncheck(
+      &Chain::Regtest.address_from_script(&script_pubkey).unwrap(),
+    ));
+
+    let script_pubkey = Some(script_pubkey.to_asm_string());
+
+    let value = 1001 * 50 * COIN_VALUE;
+
+    let ranges = (1..=1001)
+      .map(|height| Height(height).starting_sat().n())
+      .map(|start| (start, start + 50 * COIN_VALUE))
+      .collect::<Vec<(u64, u64)>>();
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatRanges>(format!("/r/utxo/{txid}:0/ranges")),
+      api::SatRanges {
+        address: address.clone(),
+        inscriptions: None,
+        more: true,
+        page: 0,
+        ranges: ranges[..1000].to_vec(),
+        script_pubkey: script_pubkey.clone(),
+        value,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatRanges>(format!("/r/utxo/{txid}:0/ranges/1")),
+      api::SatRanges {
+        address: address.clone(),
+        inscriptions: None,
+        more: false,
+        page: 1,
+        ranges: ranges[1000..].to_vec(),
+        script_pubkey: script_pubkey.clone(),
+        value,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatRanges>(format!("/r/utxo/{txid}:0/ranges/2")),
+      api::SatRanges {
+        address: address.clone(),
+        inscriptions: None,
+        more: false,
+        page: 2,
+        ranges: Vec::new(),
+        script_pubkey: script_pubkey.clone(),
+        value,
+      }
+    );
+  }
+
+  #[test]
+  fn address_rarities() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .ord_flag("--index-addresses")
+      .build();
+
+    let address = Address::from_script(
+      &ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRarities>(format!("/r/address/{address}/rarities")),
+      api::AddressRarities {
+        outputs: Vec::new()
+      }
+    );
+
+    let split = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      output_values: &[330, 50 * COIN_VALUE - 330],
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRarities>(format!("/r/address/{address}/rarities")),
+      api::AddressRarities {
+        outputs: vec![api::OutputRarities {
+          outpoint: OutPoint {
+            txid: split,
+            vout: 0
+          },
+          rare_sats: vec![api::RareSat {
+            offset: 0,
+            rarity: Rarity::Uncommon,
+            sat: Sat(50 * COIN_VALUE),
+          }],
+        }],
+      }
+    );
+
+    let send = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default()), (2, 1, 1, Default::default())],
+      p2tr: true,
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRarities>(format!("/r/address/{address}/rarities")),
+      api::AddressRarities {
+        outputs: Vec::new()
+      }
+    );
+
+    let address = Address::from_script(
+      &server.core.tx_by_id(send).output[0].script_pubkey,
+      Network::Regtest,
+    )
+    .unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRarities>(format!("/r/address/{address}/rarities")),
+      api::AddressRarities {
+        outputs: vec![api::OutputRarities {
+          outpoint: OutPoint {
+            txid: send,
+            vout: 0
+          },
+          rare_sats: vec![api::RareSat {
+            offset: 0,
+            rarity: Rarity::Uncommon,
+            sat: Sat(50 * COIN_VALUE),
+          }],
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn address_rarities_requires_address_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let address = Address::from_script(
+      &ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    server.assert_response(
+      format!("/r/address/{address}/rarities"),
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+  }
+
+  #[test]
+  fn address_rarities_rejects_address_for_other_network() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .ord_flag("--index-addresses")
+      .build();
+
+    let address = Address::from_script(
+      &ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()),
+      Network::Bitcoin,
+    )
+    .unwrap();
+
+    assert_eq!(
+      server
+        .get(format!("/r/address/{address}/rarities"))
+        .status(),
+      StatusCode::BAD_REQUEST,
+    );
+  }
+
+  #[test]
+  fn utxo_rarities_of_zero_value_and_dust_outputs() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 4,
+      output_values: &[0, 330, 50 * COIN_VALUE - 330, 0],
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::UtxoRarities>(format!("/r/utxo/{txid}:0/rarities")),
+      api::UtxoRarities {
+        rare_sats: Vec::new(),
+        sat_ranges: Vec::new(),
+        spent: false,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::UtxoRarities>(format!("/r/utxo/{txid}:1/rarities")),
+      api::UtxoRarities {
+        rare_sats: vec![api::RareSat {
+          offset: 0,
+          rarity: Rarity::Uncommon,
+          sat: Sat(50 * COIN_VALUE),
+        }],
+        sat_ranges: vec![(50 * COIN_VALUE, 50 * COIN_VALUE + 330)],
+        spent: false,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::UtxoRarities>(format!("/r/utxo/{txid}:2/rarities")),
+      api::UtxoRarities {
+        rare_sats: Vec::new(),
+        sat_ranges: vec![(50 * COIN_VALUE + 330, 100 * COIN_VALUE)],
+        spent: false,
+      }
+    );
+  }
+
+  #[test]
+  fn utxo_rarities_requires_sat_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let outpoint = OutPoint {
+      txid: server.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    server.assert_response(
+      format!("/r/utxo/{outpoint}/rarities"),
+      StatusCode::NOT_FOUND,
+      "this server has no sat index",
+    );
+  }
+
+  #[test]
+  fn children_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/r/children/{parent_inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent_inscription_id} not found"),
+    );
+
+    server.mine_blocks(1);
+
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
+    assert_eq!(children_json.ids.len(), 0);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent_inscription_id.value()],
+        unrecognized_even_field: false,
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let first_child_inscription_id = InscriptionId { txid, index: 0 };
+    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
+    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
+    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
+
+    assert_eq!(children_json.ids.len(), 100);
+    assert_eq!(children_json.ids[0], first_child_inscription_id);
+    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
+    assert!(children_json.more);
+    assert_eq!(children_json.page, 0);
+
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}/1"));
+
+    assert_eq!(children_json.ids.len(), 11);
+    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
+    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
+    assert!(!children_json.more);
+    assert_eq!(children_json.page, 1);
+  }
+
+  #[test]
+  fn inscriptions_in_block_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    for _ in 0..101 {
+      server.mine_blocks(1);
+    }
+
+    for i in 0..101 {
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/block/102",
+      StatusCode::OK,
+      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/block/102/1",
+      StatusCode::OK,
+      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_query_display() {
+    assert_eq!(
+      query::Inscription::Id(inscription_id(1)).to_string(),
+      "1111111111111111111111111111111111111111111111111111111111111111i1"
+    );
+    assert_eq!(query::Inscription::Number(1).to_string(), "1")
+  }
+
+  #[test]
+  fn inscription_not_found() {
+    TestServer::builder()
       .chain(Chain::Regtest)
       .build()
       .assert_response(
@@ -6235,6 +8574,58 @@ next
     );
   }
 
+  #[test]
+  fn content_can_be_suppressed_with_config() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
+      .build();
+
+    core.mine_blocks(3);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/foo", "hello").to_witness()),
+        (2, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (3, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let server = TestServer::builder()
+      .core(core)
+      .config(
+        "suppressed_content: [2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae]
+suppressed_content_types: [text/foo]",
+      )
+      .build();
+
+    for index in 0..2 {
+      let inscription = InscriptionId { txid, index };
+
+      server.assert_response_regex(format!("/inscription/{inscription}"), StatusCode::OK, ".*");
+
+      server.assert_response(
+        format!("/content/{inscription}"),
+        StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+        "",
+      );
+
+      server.assert_response(
+        format!("/preview/{inscription}"),
+        StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+        &PreviewUnknownHtml.to_string(),
+      );
+    }
+
+    server.assert_response(
+      format!("/content/{}", InscriptionId { txid, index: 2 }),
+      StatusCode::OK,
+      "bar",
+    );
+  }
+
   #[test]
   fn update_endpoint_is_not_available_when_not_in_integration_test_mode() {
     let server = TestServer::builder().build();
@@ -0,0 +1,91 @@
+use super::*;
+
+pub(crate) struct AcceptNdjson(pub(crate) bool);
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for AcceptNdjson
+where
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    let mut ndjson = 0.0;
+    let mut json = 0.0;
+
+    for media_range in parts
+      .headers
+      .get_all(header::ACCEPT)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .flat_map(|value| value.split(','))
+    {
+      let mut parameters = media_range.split(';');
+
+      let media_type = parameters
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+      let quality = parameters
+        .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+        .find_map(|quality| quality.trim().parse::<f32>().ok())
+        .unwrap_or(1.0);
+
+      match media_type.as_str() {
+        "application/x-ndjson" => ndjson = f32::max(ndjson, quality),
+        "application/json" | "application/*" | "*/*" => json = f32::max(json, quality),
+        _ => {}
+      }
+    }
+
+    Ok(Self(ndjson > 0.0 && ndjson >= json))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    axum::{extract::FromRequestParts, http::Request},
+  };
+
+  async fn accepts_ndjson(accept: &[&str]) -> bool {
+    let mut req = Request::builder();
+
+    for value in accept {
+      req = req.header(header::ACCEPT, *value);
+    }
+
+    AcceptNdjson::from_request_parts(&mut req.body(()).unwrap().into_parts().0, &())
+      .await
+      .unwrap()
+      .0
+  }
+
+  #[tokio::test]
+  async fn exact_media_type() {
+    assert!(accepts_ndjson(&["application/x-ndjson"]).await);
+    assert!(!accepts_ndjson(&["application/json"]).await);
+    assert!(!accepts_ndjson(&[]).await);
+  }
+
+  #[tokio::test]
+  async fn media_type_with_parameters() {
+    assert!(accepts_ndjson(&["application/x-ndjson; charset=utf-8"]).await);
+    assert!(accepts_ndjson(&["Application/X-NDJSON;q=0.5"]).await);
+  }
+
+  #[tokio::test]
+  async fn media_type_list_with_qvalues() {
+    assert!(accepts_ndjson(&["application/json;q=0.9, application/x-ndjson"]).await);
+    assert!(accepts_ndjson(&["application/x-ndjson, */*;q=0.1"]).await);
+    assert!(!accepts_ndjson(&["application/x-ndjson;q=0.5, application/json"]).await);
+    assert!(!accepts_ndjson(&["application/x-ndjson;q=0, */*"]).await);
+    assert!(accepts_ndjson(&["text/html", "application/x-ndjson"]).await);
+  }
+}
@@ -9,6 +9,7 @@ pub(super) enum ServerError {
     content_encoding: HeaderValue,
   },
   NotFound(String),
+  Timeout(Duration),
 }
 
 pub(super) type ServerResult<T = Response> = Result<T, ServerError>;
@@ -50,6 +51,14 @@ impl IntoResponse for ServerError {
         message,
       )
         .into_response(),
+      Self::Timeout(timeout) => (
+        StatusCode::SERVICE_UNAVAILABLE,
+        format!(
+          "scan did not finish within the {} scan timeout",
+          humantime::format_duration(timeout)
+        ),
+      )
+        .into_response(),
     }
   }
 }
@@ -0,0 +1,70 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Listener {
+  pub(crate) port: u16,
+  pub(crate) prefixes: Vec<String>,
+}
+
+impl Listener {
+  pub(crate) fn allows(&self, path: &str) -> bool {
+    self.prefixes.iter().any(|prefix| path.starts_with(prefix))
+  }
+}
+
+impl FromStr for Listener {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (port, prefixes) = s
+      .split_once('=')
+      .ok_or_else(|| anyhow!("listener `{s}` must be of the form `PORT=PREFIX[,PREFIX...]`"))?;
+
+    let prefixes = prefixes
+      .split(',')
+      .map(|prefix| {
+        ensure!(
+          prefix.starts_with('/'),
+          "listener route prefix `{prefix}` must start with `/`"
+        );
+        Ok(prefix.to_string())
+      })
+      .collect::<Result<Vec<String>>>()?;
+
+    Ok(Self {
+      port: port.parse()?,
+      prefixes,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "8080=/r/,/content/".parse::<Listener>().unwrap(),
+      Listener {
+        port: 8080,
+        prefixes: vec!["/r/".into(), "/content/".into()],
+      }
+    );
+
+    assert!("8080".parse::<Listener>().is_err());
+    assert!("foo=/r/".parse::<Listener>().is_err());
+    assert!("8080=r/".parse::<Listener>().is_err());
+    assert!("8080=".parse::<Listener>().is_err());
+  }
+
+  #[test]
+  fn allows() {
+    let listener = "8080=/r/,/status".parse::<Listener>().unwrap();
+
+    assert!(listener.allows("/r/blockheight"));
+    assert!(listener.allows("/status"));
+    assert!(!listener.allows("/inscriptions"));
+    assert!(!listener.allows("/"));
+  }
+}
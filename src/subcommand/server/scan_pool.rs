@@ -0,0 +1,233 @@
+use {
+  super::*,
+  std::sync::atomic::{AtomicU64, AtomicUsize},
+};
+
+/// Runs expensive sat scans on at most `threads` blocking threads at a time.
+/// Further scans wait for a free thread, so scans compete with each other
+/// rather than with block indexing and ordinary requests.
+pub(crate) struct ScanPool {
+  active: AtomicUsize,
+  completed: AtomicU64,
+  queued: AtomicUsize,
+  semaphore: Arc<Semaphore>,
+  threads: usize,
+  timeout: Option<Duration>,
+}
+
+impl ScanPool {
+  pub(crate) fn new(threads: usize, timeout: Option<Duration>) -> Self {
+    Self {
+      active: AtomicUsize::new(0),
+      completed: AtomicU64::new(0),
+      queued: AtomicUsize::new(0),
+      semaphore: Arc::new(Semaphore::new(threads)),
+      threads,
+      timeout,
+    }
+  }
+
+  /// Runs `f` on the pool, passing it a flag that is set once nobody is
+  /// waiting for its result any more, either because the request was dropped
+  /// when the client disconnected, or because the scan timeout elapsed. Scans
+  /// should check the flag regularly and give up once it is set.
+  pub(crate) async fn run<T, F>(self: &Arc<Self>, f: F) -> ServerResult<T>
+  where
+    F: FnOnce(&AtomicBool) -> ServerResult<T> + Send + 'static,
+    T: Send + 'static,
+  {
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let _cancel = Cancel(cancelled.clone());
+
+    let scan = async {
+      let permit = {
+        let _queued = Gauge::increment(&self.queued);
+        self
+          .semaphore
+          .clone()
+          .acquire_owned()
+          .await
+          .map_err(|err| ServerError::Internal(err.into()))?
+      };
+
+      let pool = self.clone();
+      let cancelled = cancelled.clone();
+
+      task::spawn_blocking(move || {
+        let result = {
+          let _active = Gauge::increment(&pool.active);
+          f(&cancelled)
+        };
+        pool.completed.fetch_add(1, atomic::Ordering::Relaxed);
+        drop(permit);
+        result
+      })
+      .await
+      .map_err(|err| ServerError::Internal(err.into()))?
+    };
+
+    match self.timeout {
+      Some(timeout) => tokio::time::timeout(timeout, scan)
+        .await
+        .map_err(|_| ServerError::Timeout(timeout))?,
+      None => scan.await,
+    }
+  }
+
+  pub(crate) fn status(&self) -> api::ScanPool {
+    api::ScanPool {
+      active: self.active.load(atomic::Ordering::Relaxed),
+      completed: self.completed.load(atomic::Ordering::Relaxed),
+      queued: self.queued.load(atomic::Ordering::Relaxed),
+      threads: self.threads,
+    }
+  }
+}
+
+struct Cancel(Arc<AtomicBool>);
+
+impl Drop for Cancel {
+  fn drop(&mut self) {
+    self.0.store(true, atomic::Ordering::Relaxed);
+  }
+}
+
+/// Counts something for as long as it is alive, including when a request is
+/// dropped while waiting for a thread.
+struct Gauge<'a>(&'a AtomicUsize);
+
+impl<'a> Gauge<'a> {
+  fn increment(count: &'a AtomicUsize) -> Self {
+    count.fetch_add(1, atomic::Ordering::Relaxed);
+    Self(count)
+  }
+}
+
+impl Drop for Gauge<'_> {
+  fn drop(&mut self) {
+    self.0.fetch_sub(1, atomic::Ordering::Relaxed);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn scans_beyond_thread_count_are_queued() {
+    let pool = Arc::new(ScanPool::new(1, None));
+
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (finish_tx, finish_rx) = std::sync::mpsc::channel::<()>();
+
+    let first = tokio::spawn({
+      let pool = pool.clone();
+      async move {
+        pool
+          .run(move |_| {
+            started_tx.send(()).unwrap();
+            finish_rx.recv().unwrap();
+            Ok(1)
+          })
+          .await
+      }
+    });
+
+    task::spawn_blocking(move || started_rx.recv().unwrap())
+      .await
+      .unwrap();
+
+    let second = tokio::spawn({
+      let pool = pool.clone();
+      async move { pool.run(|_| Ok(2)).await }
+    });
+
+    while pool.status().queued == 0 {
+      tokio::task::yield_now().await;
+    }
+
+    assert_eq!(
+      pool.status(),
+      api::ScanPool {
+        active: 1,
+        completed: 0,
+        queued: 1,
+        threads: 1,
+      }
+    );
+
+    finish_tx.send(()).unwrap();
+
+    assert_eq!(first.await.unwrap().unwrap(), 1);
+    assert_eq!(second.await.unwrap().unwrap(), 2);
+
+    assert_eq!(
+      pool.status(),
+      api::ScanPool {
+        active: 0,
+        completed: 2,
+        queued: 0,
+        threads: 1,
+      }
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn scans_are_cancelled_after_timeout() {
+    let pool = Arc::new(ScanPool::new(1, Some(Duration::from_millis(100))));
+
+    let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel();
+
+    assert!(matches!(
+      pool
+        .run(move |cancelled| {
+          while !cancelled.load(atomic::Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(10));
+          }
+          cancelled_tx.send(()).unwrap();
+          Ok(())
+        })
+        .await,
+      Err(ServerError::Timeout(_)),
+    ));
+
+    task::spawn_blocking(move || cancelled_rx.recv().unwrap())
+      .await
+      .unwrap();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn scans_are_cancelled_when_request_is_dropped() {
+    let pool = Arc::new(ScanPool::new(1, None));
+
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel();
+
+    let request = tokio::spawn({
+      let pool = pool.clone();
+      async move {
+        pool
+          .run(move |cancelled| {
+            started_tx.send(()).unwrap();
+            while !cancelled.load(atomic::Ordering::Relaxed) {
+              thread::sleep(Duration::from_millis(10));
+            }
+            cancelled_tx.send(()).unwrap();
+            Ok(())
+          })
+          .await
+      }
+    });
+
+    task::spawn_blocking(move || started_rx.recv().unwrap())
+      .await
+      .unwrap();
+
+    request.abort();
+
+    task::spawn_blocking(move || cancelled_rx.recv().unwrap())
+      .await
+      .unwrap();
+  }
+}
@@ -0,0 +1,114 @@
+use {
+  super::*,
+  std::{hash::Hash, sync::OnceLock},
+};
+
+/// Coalesces concurrent calls with the same key, so that when many requests
+/// ask for the same thing at once, it is only computed once and every caller
+/// receives a copy of the result. Results are not kept once all concurrent
+/// callers have been answered.
+pub(crate) struct SingleFlight<K, V> {
+  calls: Mutex<HashMap<K, Arc<OnceLock<V>>>>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> SingleFlight<K, V> {
+  pub(crate) fn new() -> Self {
+    Self {
+      calls: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Returns the result of `f`, or of a concurrent call with the same key if
+  /// one is in flight. Blocks until the result is available, so must be called
+  /// from a blocking thread.
+  pub(crate) fn run(&self, key: K, f: impl FnOnce() -> V) -> V {
+    let call = self
+      .calls
+      .lock()
+      .unwrap()
+      .entry(key.clone())
+      .or_default()
+      .clone();
+
+    let value = call.get_or_init(f).clone();
+
+    let mut calls = self.calls.lock().unwrap();
+
+    if calls
+      .get(&key)
+      .is_some_and(|current| Arc::ptr_eq(current, &call))
+    {
+      calls.remove(&key);
+    }
+
+    value
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::sync::atomic::AtomicUsize};
+
+  #[test]
+  fn concurrent_calls_are_coalesced() {
+    let single_flight = Arc::new(SingleFlight::<u32, u32>::new());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (finish_tx, finish_rx) = std::sync::mpsc::channel::<()>();
+
+    let first = thread::spawn({
+      let single_flight = single_flight.clone();
+      let calls = calls.clone();
+      move || {
+        single_flight.run(1, || {
+          calls.fetch_add(1, atomic::Ordering::Relaxed);
+          started_tx.send(()).unwrap();
+          finish_rx.recv().unwrap();
+          10
+        })
+      }
+    });
+
+    started_rx.recv().unwrap();
+
+    let second = thread::spawn({
+      let single_flight = single_flight.clone();
+      let calls = calls.clone();
+      move || {
+        single_flight.run(1, || {
+          calls.fetch_add(1, atomic::Ordering::Relaxed);
+          20
+        })
+      }
+    });
+
+    assert_eq!(
+      single_flight.run(2, || {
+        calls.fetch_add(1, atomic::Ordering::Relaxed);
+        30
+      }),
+      30
+    );
+
+    // wait until the second call holds the first call's cell
+    while Arc::strong_count(&single_flight.calls.lock().unwrap()[&1]) < 3 {
+      thread::yield_now();
+    }
+
+    finish_tx.send(()).unwrap();
+
+    assert_eq!(first.join().unwrap(), 10);
+    assert_eq!(second.join().unwrap(), 10);
+    assert_eq!(calls.load(atomic::Ordering::Relaxed), 2);
+    assert!(single_flight.calls.lock().unwrap().is_empty());
+  }
+
+  #[test]
+  fn results_are_not_kept() {
+    let single_flight = SingleFlight::<u32, u32>::new();
+
+    assert_eq!(single_flight.run(1, || 10), 10);
+    assert_eq!(single_flight.run(1, || 20), 20);
+  }
+}
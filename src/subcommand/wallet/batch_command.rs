@@ -34,6 +34,24 @@ impl Batch {
         .map(|(satpoint, txout)| (satpoint.outpoint, txout.clone())),
     );
 
+    let mut locked_utxos = locked_utxos.into_keys().collect::<BTreeSet<OutPoint>>();
+
+    let satpoint = if let Some(sat) = batchfile.sat {
+      Some(wallet.find_sat_in_outputs(sat)?)
+    } else {
+      batchfile.satpoint
+    };
+
+    if self.shared.protect_rare_sats {
+      let mut rare_sat_outputs = wallet.get_rare_sat_outputs()?;
+
+      if let Some(satpoint) = satpoint {
+        rare_sat_outputs.remove(&satpoint.outpoint);
+      }
+
+      locked_utxos.extend(rare_sat_outputs);
+    }
+
     if let Some(etching) = batchfile.etching {
       Self::check_etching(&wallet, &etching)?;
     }
@@ -52,18 +70,9 @@ impl Batch {
       reinscribe: batchfile.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints,
-      satpoint: if let Some(sat) = batchfile.sat {
-        Some(wallet.find_sat_in_outputs(sat)?)
-      } else {
-        batchfile.satpoint
-      },
+      satpoint,
     }
-    .inscribe(
-      &locked_utxos.into_keys().collect(),
-      wallet.get_runic_outputs()?,
-      utxos,
-      &wallet,
-    )
+    .inscribe(&locked_utxos, wallet.get_runic_outputs()?, utxos, &wallet)
   }
 
   fn check_etching(wallet: &Wallet, etching: &batch::Etching) -> Result {
@@ -59,6 +59,28 @@ impl Inscribe {
       }
     }
 
+    let satpoint = if let Some(sat) = self.sat {
+      Some(wallet.find_sat_in_outputs(sat)?)
+    } else {
+      self.satpoint
+    };
+
+    let mut locked_utxos = wallet
+      .locked_utxos()
+      .keys()
+      .cloned()
+      .collect::<BTreeSet<OutPoint>>();
+
+    if self.shared.protect_rare_sats {
+      let mut rare_sat_outputs = wallet.get_rare_sat_outputs()?;
+
+      if let Some(satpoint) = satpoint {
+        rare_sat_outputs.remove(&satpoint.outpoint);
+      }
+
+      locked_utxos.extend(rare_sat_outputs);
+    }
+
     batch::Plan {
       commit_fee_rate: self.shared.commit_fee_rate.unwrap_or(self.shared.fee_rate),
       destinations: vec![match self.destination.clone() {
@@ -86,14 +108,10 @@ impl Inscribe {
       reinscribe: self.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints: Vec::new(),
-      satpoint: if let Some(sat) = self.sat {
-        Some(wallet.find_sat_in_outputs(sat)?)
-      } else {
-        self.satpoint
-      },
+      satpoint,
     }
     .inscribe(
-      &wallet.locked_utxos().clone().into_keys().collect(),
+      &locked_utxos,
       wallet.get_runic_outputs()?,
       wallet.utxos(),
       &wallet,
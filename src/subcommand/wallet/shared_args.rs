@@ -21,4 +21,9 @@ pub(super) struct SharedArgs {
     help = "Do not check that transactions are equal to or below the MAX_STANDARD_TX_WEIGHT of 400,000 weight units. Transactions over this limit are currently nonstandard and will not be relayed by bitcoind in its default configuration. Do not use this flag unless you understand the implications."
   )]
   pub(crate) no_limit: bool,
+  #[arg(
+    long,
+    help = "Do not spend outputs containing rare sats. Requires ord server with `--index-sats`."
+  )]
+  pub(crate) protect_rare_sats: bool,
 }
@@ -6,18 +6,23 @@ pub struct StatusHtml {
   pub chain: Chain,
   pub content_type_counts: Vec<(Option<Vec<u8>>, u64)>,
   pub cursed_inscriptions: u64,
+  pub git_branch: Option<String>,
+  pub git_commit: Option<String>,
   pub height: Option<u32>,
   pub initial_sync_time: Duration,
   pub inscriptions: u64,
+  pub instance_id: Option<String>,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
   pub rune_index: bool,
   pub runes: u64,
   pub sat_index: bool,
+  pub scan_pool: api::ScanPool,
   pub started: DateTime<Utc>,
   pub transaction_index: bool,
   pub unrecoverably_reorged: bool,
   pub uptime: Duration,
+  pub version: String,
 }
 
 impl PageContent for StatusHtml {
@@ -206,6 +206,26 @@ impl Wallet {
     }
   }
 
+  pub(crate) fn get_rare_sat_outputs(&self) -> Result<BTreeSet<OutPoint>> {
+    ensure!(
+      self.has_sat_index,
+      "ord index must be built with `--index-sats` to use `--protect-rare-sats`"
+    );
+
+    Ok(
+      self
+        .get_output_sat_ranges()?
+        .into_iter()
+        .filter(|(_, sat_ranges)| {
+          sat_ranges
+            .iter()
+            .any(|(start, _end)| Sat(*start).rarity() > Rarity::Common)
+        })
+        .map(|(output, _)| output)
+        .collect(),
+    )
+  }
+
   pub(crate) fn get_runic_outputs(&self) -> Result<BTreeSet<OutPoint>> {
     let mut runic_outputs = BTreeSet::new();
     for (output, info) in self.output_info.iter() {
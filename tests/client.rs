@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn outputs() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats", "--regtest"], &[]);
+
+  let outpoint = OutPoint {
+    txid: core.mine_blocks(1)[0].txdata[0].txid(),
+    vout: 0,
+  };
+
+  let expected = serde_json::from_str::<api::Output>(
+    &ord
+      .json_request(format!("/output/{outpoint}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "--regtest client --server-url {} outputs {outpoint}",
+    ord.url()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<Vec<api::Output>>();
+
+  pretty_assert_eq!(output, vec![expected]);
+}
+
+#[test]
+fn sat() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let sat = CommandBuilder::new(format!("client --server-url {} sat 0", ord.url()))
+    .core(&core)
+    .run_and_deserialize_output::<api::Sat>();
+
+  assert_eq!(sat.number, 0);
+  assert_eq!(sat.rarity, Rarity::Mythic);
+}
+
+#[test]
+fn errors_are_reported() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  CommandBuilder::new(format!(
+    "client --server-url {} inscription {}",
+    ord.url(),
+    "0000000000000000000000000000000000000000000000000000000000000000i0"
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .stderr_regex("error: /inscription/0{64}i0: 404 Not Found: .*\n")
+  .run_and_extract_stdout();
+}
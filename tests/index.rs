@@ -52,6 +52,70 @@ fn re_opening_database_does_not_trigger_schema_check() {
     .run_and_extract_stdout();
 }
 
+#[test]
+fn export_and_import_snapshot() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let snapshot_path = tempdir.path().join("foo.snapshot");
+  let imported_path = tempdir.path().join("bar.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  let header = CommandBuilder::new(format!(
+    "--index {} index export-snapshot {}",
+    index_path.display(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::index::snapshot::Header>();
+
+  assert_eq!(header.chain, Chain::Mainnet);
+  assert_eq!(header.height, Some(2));
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} index import-snapshot {}",
+      imported_path.display(),
+      snapshot_path.display()
+    ))
+    .run_and_deserialize_output::<ord::index::snapshot::Header>(),
+    header,
+  );
+
+  CommandBuilder::new(format!(
+    "--index {} index import-snapshot {}",
+    imported_path.display(),
+    snapshot_path.display()
+  ))
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: index already exists at `{}`, delete it before importing a snapshot\n",
+    imported_path.display()
+  ))
+  .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index {} index update", imported_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} --regtest index import-snapshot {}",
+    tempdir.path().join("baz.redb").display(),
+    snapshot_path.display()
+  ))
+  .expected_exit_code(1)
+  .expected_stderr("error: snapshot is for mainnet, not regtest\n")
+  .run_and_extract_stdout();
+}
+
 #[test]
 fn export_inscription_number_to_id_tsv() {
   let core = mockcore::spawn();
@@ -90,3 +154,205 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn export_at_height() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let create_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let transfer_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 1, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let inscription_id = InscriptionId {
+    txid: create_txid,
+    index: 0,
+  };
+
+  for (height, txid) in [(1, None), (2, Some(create_txid)), (3, Some(transfer_txid))] {
+    let tsv = CommandBuilder::new(format!(
+      "--index-inscription-history index export --tsv foo.tsv --height {height}"
+    ))
+    .core(&core)
+    .run_and_extract_file("foo.tsv");
+
+    let mut lines = tsv.lines();
+
+    assert_eq!(
+      lines.next().unwrap(),
+      format!("# export at block height {}", height + 1)
+    );
+
+    match txid {
+      Some(txid) => assert_eq!(
+        lines.next().unwrap(),
+        format!("0\t{inscription_id}\t{txid}:0:0")
+      ),
+      None => assert_eq!(lines.next(), None),
+    }
+  }
+
+  CommandBuilder::new("--index-inscription-history index export --tsv foo.tsv --height 4")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: cannot export at height 4, index has 4 blocks\n")
+    .run_and_extract_stdout();
+
+  CommandBuilder::new("index export --tsv foo.tsv --height 2")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: exporting at a height requires index created with `--index-inscription-history` flag\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn holders_at_height() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let create_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let transfer_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 1, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let inscription_id = InscriptionId {
+    txid: create_txid,
+    index: 0,
+  };
+
+  for (height, txid) in [(1, None), (2, Some(create_txid)), (3, Some(transfer_txid))] {
+    let csv = CommandBuilder::new(format!(
+      "--index-inscription-history index holders --inscriptions {inscription_id} --height {height} --csv holders.csv"
+    ))
+    .core(&core)
+    .run_and_extract_file("holders.csv");
+
+    let mut lines = csv.lines();
+
+    assert_eq!(lines.next(), Some("inscription_id,satpoint,address"));
+
+    match txid {
+      Some(txid) => assert!(lines
+        .next()
+        .unwrap()
+        .starts_with(&format!("{inscription_id},{txid}:0:0,"))),
+      None => assert_eq!(lines.next(), None),
+    }
+  }
+}
+
+#[test]
+fn provenance() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let create_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let transfer_txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 1, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let inscription_id = InscriptionId {
+    txid: create_txid,
+    index: 0,
+  };
+
+  let output = CommandBuilder::new(format!(
+    "--index-inscription-history index provenance {inscription_id}"
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::provenance::Output>();
+
+  assert_eq!(output.inscription_id, inscription_id);
+
+  assert_eq!(
+    output
+      .locations
+      .iter()
+      .map(|location| (location.height, location.satpoint.outpoint.txid))
+      .collect::<Vec<(u32, Txid)>>(),
+    [(2, create_txid), (3, transfer_txid)],
+  );
+
+  assert!(output
+    .locations
+    .iter()
+    .all(|location| location.address.is_some()));
+}
+
+#[test]
+fn provenance_of_unknown_inscription_is_an_error() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(
+    "--index-inscription-history index provenance 0000000000000000000000000000000000000000000000000000000000000000i0",
+  )
+  .core(&core)
+  .expected_stderr(
+    "error: inscription 0000000000000000000000000000000000000000000000000000000000000000i0 not found\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn provenance_requires_inscription_history_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(
+    "index provenance 0000000000000000000000000000000000000000000000000000000000000000i0",
+  )
+  .core(&core)
+  .expected_stderr(
+    "error: inscription location history requires index created with `--index-inscription-history` flag\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -461,8 +461,16 @@ fn get_transaction() {
 fn get_status() {
   let core = mockcore::builder().network(Network::Regtest).build();
 
-  let ord =
-    TestServer::spawn_with_server_args(&core, &["--regtest", "--index-sats", "--index-runes"], &[]);
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &[
+      "--regtest",
+      "--index-sats",
+      "--index-runes",
+      "--instance-id=ord-1",
+    ],
+    &[],
+  );
 
   create_wallet(&core, &ord);
   core.mine_blocks(1);
@@ -492,18 +500,32 @@ fn get_status() {
       chain: Chain::Regtest,
       content_type_counts: vec![(Some("text/plain;charset=utf-8".into()), 1)],
       cursed_inscriptions: 0,
+      git_branch: Some(env!("GIT_BRANCH"))
+        .filter(|branch| !branch.is_empty())
+        .map(Into::into),
+      git_commit: Some(env!("GIT_COMMIT"))
+        .filter(|commit| !commit.is_empty())
+        .map(Into::into),
       height: Some(3),
       initial_sync_time: dummy_duration,
       inscriptions: 1,
+      instance_id: Some("ord-1".into()),
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
       rune_index: true,
       runes: 0,
       sat_index: true,
+      scan_pool: api::ScanPool {
+        active: 0,
+        completed: 0,
+        queued: 0,
+        threads: 2,
+      },
       started: dummy_started,
       transaction_index: false,
       unrecoverably_reorged: false,
       uptime: dummy_duration,
+      version: env!("CARGO_PKG_VERSION").into(),
     }
   );
 }
@@ -55,6 +55,7 @@ mod expected;
 mod test_server;
 
 mod balances;
+mod client;
 mod decode;
 mod epochs;
 mod find;
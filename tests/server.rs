@@ -574,6 +574,28 @@ fn run_no_sync() {
   child.kill().unwrap();
 }
 
+#[test]
+fn listener_that_fails_to_bind_fails_server() {
+  let core = mockcore::spawn();
+
+  let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+
+  let port = TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+
+  CommandBuilder::new(format!(
+    "server --address 127.0.0.1 --http-port {port} --listener {}=/r/",
+    taken.local_addr().unwrap().port()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .stderr_regex("error: .*in use.*\n")
+  .run_and_extract_stdout();
+}
+
 #[test]
 fn authentication() {
   let core = mockcore::spawn();
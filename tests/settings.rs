@@ -16,20 +16,35 @@ fn default() {
   "config_dir": null,
   "cookie_file": ".*\.cookie",
   "data_dir": ".*",
+  "event_filter": null,
   "first_inscription_height": 767430,
+  "flagged": \[\],
+  "flagged_content": \[\],
   "height_limit": null,
   "hidden": \[\],
   "index": ".*index\.redb",
+  "index_addresses": false,
   "index_cache_size": \d+,
+  "index_inscription_history": false,
   "index_runes": false,
   "index_sats": false,
   "index_spent_sats": false,
   "index_transactions": false,
+  "inscription_digests": false,
+  "instance_id": null,
   "integration_test": false,
+  "max_inscription_body_size": null,
+  "max_inscription_metadata_size": null,
+  "new_supply_events": false,
   "no_index_inscriptions": false,
+  "sanctioned_addresses": null,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "suppress_self_transfers": false,
+  "suppressed_content": \[\],
+  "suppressed_content_types": \[\],
+  "untracked_rarities": \[\]
 \}
 "#,
     )
@@ -1264,3 +1264,47 @@ fn file_inscribe_with_non_existent_delegate_inscription() {
   .expected_exit_code(1)
   .run_and_extract_stdout();
 }
+
+#[test]
+fn inscribe_does_not_spend_rare_sats_with_protect_rare_sats() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate 1 --protect-rare-sats")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: wallet contains no cardinal utxos\n")
+    .run_and_extract_stdout();
+
+  CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate 1")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+}
+
+#[test]
+fn protect_rare_sats_requires_sat_index() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate 1 --protect-rare-sats")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: ord index must be built with `--index-sats` to use `--protect-rare-sats`\n",
+    )
+    .run_and_extract_stdout();
+}